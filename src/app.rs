@@ -1,43 +1,151 @@
-use crate::core::commits::{get_commits_per_week, Commits, SumWeeklyCommits};
-use crate::github::api::get_contributors;
-use crate::github::contributors::serialize_contributors;
+use crate::core::commits::{get_commits_per_week, rank_authors, Commits, SumWeeklyCommits};
+use crate::error::Error;
+use crate::github::api::{
+    contributors_url, get_contributors_notifying, ApiOptions, ApiResponse, RateLimit,
+};
+use crate::github::contributors::{apply_aliases, serialize_contributors, Contributor};
+use crate::github::repository::parse_repository;
+use crate::ui::colors::Colors;
+use crate::ui::theme::Theme;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Color;
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
 use tui_input::Input;
 
 pub enum InputMode {
     Normal,
     Editing,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ViewMode {
+    Bars,
+    Lines,
+    Bump,
+    Heatmap,
+}
+
+/// Amount of racers, `auto` fits as many as the terminal height allows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Users {
+    Auto,
+    Count(usize),
+}
+
+impl FromStr for Users {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Users::Auto);
+        }
+        match s.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(Users::Count(count)),
+            _ => Err(format!("'{}' is not 'auto' or a positive number", s)),
+        }
+    }
+}
+
+impl fmt::Display for Users {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Users::Auto => write!(f, "auto"),
+            Users::Count(count) => write!(f, "{}", count),
+        }
+    }
+}
+
+impl ViewMode {
+    pub fn next(self) -> Self {
+        match self {
+            ViewMode::Bars => ViewMode::Lines,
+            ViewMode::Lines => ViewMode::Bump,
+            ViewMode::Bump => ViewMode::Heatmap,
+            ViewMode::Heatmap => ViewMode::Bars,
+        }
+    }
+}
+
+/// Messages of the thread downloading the contributors
+pub enum Download {
+    /// Github asked to wait, the request is sent again after the delay
+    Retrying(Duration),
+    Done(Result<ApiResponse, Error>),
+}
+
 pub struct App {
+    pub agent: ureq::Agent,
+    pub aliases: HashMap<String, String>,
+    pub api_options: ApiOptions,
     pub author_blacklist: Vec<String>,
+    pub bar_areas: Vec<Rect>,
     pub commits: Option<Commits>,
+    pub colors: Colors,
+    pub compact: bool,
     pub current_tick: u32,
     pub current_tick_authors: Option<Vec<(String, u32)>>,
     pub current_week: Option<u32>,
+    pub detail_author: Option<String>,
+    /// Download running on another thread, the UI keeps drawing meanwhile
+    pub download: Option<Receiver<Download>>,
     pub error: Option<String>,
     pub input: Input,
     pub input_mode: InputMode,
+    pub rate_limit: Option<RateLimit>,
     pub repository_url: String,
+    /// When the download is sent again after Github asked to wait
+    pub retry_at: Option<Instant>,
+    pub rolling_ranks: bool,
+    /// Login of the selected racer, it stays selected when its rank changes
+    pub selected: Option<String>,
     pub should_load_repository: bool,
     pub should_quit: bool,
+    pub theme: Theme,
+    pub users: Users,
     pub users_to_show: usize,
+    pub view_mode: ViewMode,
 }
 
 impl App {
-    pub fn new(author_blacklist: Vec<String>, users_to_show: usize) -> Self {
+    pub fn new(author_blacklist: Vec<String>, users: Users, compact: bool) -> Self {
+        let users_to_show = match users {
+            Users::Auto => 1,
+            Users::Count(count) => count,
+        };
         Self {
+            agent: ureq::Agent::new(),
+            aliases: HashMap::new(),
+            api_options: ApiOptions::default(),
             author_blacklist,
+            bar_areas: Vec::new(),
+            colors: Colors::default(),
             commits: None,
+            compact,
             current_tick: 0,
             current_tick_authors: None,
             current_week: None,
+            detail_author: None,
+            download: None,
             error: None,
             input: Input::default(),
             input_mode: InputMode::Editing,
+            rate_limit: None,
             repository_url: "".into(),
+            retry_at: None,
+            rolling_ranks: false,
+            selected: None,
             should_load_repository: false,
             should_quit: false,
+            theme: Theme::default(),
+            users,
             users_to_show,
+            view_mode: ViewMode::Bars,
         }
     }
 
@@ -46,89 +154,228 @@ impl App {
             self.load_repository_insights();
             self.should_load_repository = false;
         }
+        self.poll_download();
         if !self.should_load_repository
+            && self.detail_author.is_none()
             && self.commits.is_some()
             && self.current_tick <= total_ticks
         {
-            let sum_weekly = self.get_week_on_tick(self.current_tick, total_ticks);
-            let authors = self.get_sorted_authors(sum_weekly.0);
-            self.current_week = Some(*sum_weekly.1);
-            self.current_tick_authors = Some(authors);
+            if let Some((sum_weekly, week)) = self.get_week_on_tick(self.current_tick, total_ticks)
+            {
+                let authors = self.get_sorted_authors(sum_weekly);
+                self.current_week = Some(*week);
+                self.current_tick_authors = Some(authors);
+            }
             self.current_tick += 1;
         }
     }
 
     pub fn on_key(&mut self, c: char) {
         match c {
-            'q' => {
-                self.should_quit = true;
-            }
+            'q' => self.should_quit = true,
+            'v' => self.view_mode = self.view_mode.next(),
+            'r' => self.rolling_ranks = !self.rolling_ranks,
+            'c' => self.compact = !self.compact,
             _ => {}
         }
     }
 
-    fn load_repository_insights(&mut self) {
-        match get_contributors(
-            format!(
-                "https://github.com/{}/graphs/contributors-data",
-                &self.repository_url.to_string()
-            )
-            .as_str(),
-        ) {
-            Ok(content) => {
-                let contributors = serialize_contributors(content.as_str()).unwrap();
-                let commits = get_commits_per_week(contributors, self.author_blacklist.clone());
-                self.error = None;
-                self.commits = Some(commits);
+    pub fn on_up(&mut self) {
+        if self.detail_author.is_none() {
+            let index = self.selected_index().map_or(0, |i| i.saturating_sub(1));
+            self.select(index);
+        }
+    }
+
+    pub fn on_down(&mut self) {
+        if self.detail_author.is_none() {
+            let last = self.visible_authors().saturating_sub(1);
+            let index = self.selected_index().map_or(0, |i| (i + 1).min(last));
+            self.select(index);
+        }
+    }
+
+    pub fn on_enter(&mut self) {
+        if self.selected.is_some() {
+            self.detail_author = self.selected.clone();
+        }
+    }
+
+    pub fn on_esc(&mut self) {
+        self.detail_author = None;
+    }
+
+    pub fn on_click(&mut self, column: u16, row: u16) {
+        if self.detail_author.is_some() {
+            return;
+        }
+        let position = Position::new(column, row);
+        if let Some(index) = self.bar_areas.iter().position(|a| a.contains(position)) {
+            self.select(index);
+            self.on_enter();
+        }
+    }
+
+    /// Updates the amount of racers when they are picked automatically from the
+    /// amount of `rows` that fit on the screen
+    pub fn fit_users_to_rows(&mut self, rows: usize) {
+        if self.users == Users::Auto {
+            self.users_to_show = rows.max(1);
+        }
+    }
+
+    pub fn selected_author(&self) -> Option<&str> {
+        self.selected.as_deref()
+    }
+
+    /// Rank of the selected racer among the visible ones, none when it is off screen
+    pub fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.current_tick_authors
+            .as_ref()?
+            .iter()
+            .take(self.users_to_show)
+            .position(|(author, _)| author == selected)
+    }
+
+    /// Selects the racer at the rank, the selection is kept when nobody is there
+    fn select(&mut self, index: usize) {
+        if let Some((author, _)) = self
+            .current_tick_authors
+            .as_ref()
+            .and_then(|authors| authors.get(index))
+        {
+            self.selected = Some(author.clone());
+        }
+    }
+
+    fn visible_authors(&self) -> usize {
+        self.current_tick_authors
+            .as_ref()
+            .map_or(0, |authors| authors.len().min(self.users_to_show))
+    }
+
+    /// Schedules the download of the repository on the next tick, the prompt stays
+    /// open with an error when it is not a Github repository
+    pub fn load_repository(&mut self, repository: &str) {
+        match parse_repository(repository) {
+            Ok(repository) => {
+                self.repository_url = repository;
+                self.input_mode = InputMode::Normal;
+                self.should_load_repository = true;
             }
             Err(e) => {
                 self.input_mode = InputMode::Editing;
-                self.error = Some(format!("{}", e));
+                self.error = Some(e);
             }
         }
     }
 
-    pub fn load_repository_insights_from_json(&mut self, json_path_file: &str) {
-        let file_content_result = fs::read_to_string(json_path_file);
-        match file_content_result {
-            Ok(file_content) => {
-                let contributors = serialize_contributors(file_content.as_str()).unwrap();
-                let commits = get_commits_per_week(contributors, self.author_blacklist.clone());
-                self.error = None;
-                self.commits = Some(commits);
-                self.input_mode = InputMode::Normal;
-            }
-            Err(e) => {
-                self.error = Some(format!("{}", e));
+    /// Starts the download on another thread, the retries of the backoff can wait for
+    /// a minute and the UI must keep answering meanwhile
+    fn load_repository_insights(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        let agent = self.agent.clone();
+        let url = contributors_url(&self.repository_url);
+        let options = self.api_options.clone();
+        thread::spawn(move || {
+            let result = get_contributors_notifying(&agent, url.as_str(), &options, |delay| {
+                let _ = sender.send(Download::Retrying(delay));
+            });
+            let _ = sender.send(Download::Done(result));
+        });
+        self.download = Some(receiver);
+    }
+
+    /// Applies the messages of the download thread received since the last tick
+    fn poll_download(&mut self) {
+        let Some(download) = self.download.as_ref() else {
+            return;
+        };
+        let result = loop {
+            match download.try_recv() {
+                Ok(Download::Retrying(delay)) => self.retry_at = Some(Instant::now() + delay),
+                Ok(Download::Done(result)) => break result,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    break Err(Error::Network {
+                        url: contributors_url(&self.repository_url),
+                        message: "the download stopped unexpectedly".to_string(),
+                    })
+                }
             }
+        };
+        self.download = None;
+        self.retry_at = None;
+        let result = result
+            .and_then(|response| {
+                self.rate_limit = Some(response.rate_limit);
+                serialize_contributors(response.body.as_str())
+            })
+            .and_then(|contributors| self.set_contributors(contributors));
+        if let Err(e) = result {
+            self.input_mode = InputMode::Editing;
+            self.error = Some(e.to_string());
         }
     }
 
-    fn get_week_on_tick(&self, tick_count: u32, total_ticks: u32) -> (&SumWeeklyCommits, &u32) {
-        let commits = self.commits.as_ref().unwrap();
-        let weeks_per_tick = (commits.total_weeks as f64 / total_ticks as f64).ceil() as u32;
-        let week_index = (tick_count * weeks_per_tick) as usize;
-        let mut commits_keys: Vec<&u32> = commits.sum_commits.keys().collect();
-        commits_keys.sort();
-        if week_index >= commits_keys.len() {
-            (
-                commits
-                    .sum_commits
-                    .get(commits_keys.last().unwrap())
-                    .unwrap(),
-                commits_keys.last().unwrap(),
-            )
+    /// Time left before the download is retried, while Github asks to wait
+    pub fn retry_in(&self) -> Option<Duration> {
+        self.retry_at
+            .map(|retry_at| retry_at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Styles of the screen, and colours of the racers chosen by the user unless the
+    /// theme has no colours
+    pub fn set_theme(&mut self, theme: Theme, colors: HashMap<String, Color>) {
+        let colors = Colors::new(colors);
+        self.colors = if theme.monochrome {
+            colors.monochrome()
         } else {
-            let week = commits_keys.get(week_index).unwrap();
-            (commits.sum_commits.get(week).unwrap(), week)
+            colors
+        };
+        self.theme = theme;
+    }
+
+    pub fn load_repository_insights_from_json(&mut self, json_path_file: &str) {
+        let result = fs::read_to_string(json_path_file)
+            .map_err(|source| Error::Io {
+                path: json_path_file.to_string(),
+                source,
+            })
+            .and_then(|file_content| serialize_contributors(file_content.as_str()))
+            .and_then(|contributors| self.set_contributors(contributors));
+        match result {
+            Ok(()) => self.input_mode = InputMode::Normal,
+            Err(e) => self.error = Some(e.to_string()),
         }
     }
 
+    pub fn set_contributors(&mut self, contributors: Vec<Contributor>) -> Result<(), Error> {
+        let contributors = apply_aliases(contributors, &self.aliases);
+        let commits = get_commits_per_week(contributors, self.author_blacklist.clone())?;
+        self.error = None;
+        self.commits = Some(commits);
+        Ok(())
+    }
+
+    fn get_week_on_tick(
+        &self,
+        tick_count: u32,
+        total_ticks: u32,
+    ) -> Option<(&SumWeeklyCommits, &u32)> {
+        let commits = self.commits.as_ref()?;
+        let weeks_per_tick = (commits.total_weeks as f64 / total_ticks.max(1) as f64).ceil() as u32;
+        let week_index = tick_count.saturating_mul(weeks_per_tick) as usize;
+        let mut commits_keys: Vec<&u32> = commits.sum_commits.keys().collect();
+        commits_keys.sort();
+        let week = commits_keys
+            .get(week_index)
+            .or_else(|| commits_keys.last())?;
+        Some((commits.sum_commits.get(week)?, week))
+    }
+
     fn get_sorted_authors(&self, sum_weekly_commits: &SumWeeklyCommits) -> Vec<(String, u32)> {
-        let authors: Vec<(&String, &u32)> = sum_weekly_commits.authors.iter().collect();
-        let mut sorted_authors: Vec<(String, u32)> =
-            authors.iter().map(|(a, c)| (a.to_string(), **c)).collect();
-        sorted_authors.sort_by(|a, b| b.1.cmp(&a.1));
-        sorted_authors
+        rank_authors(sum_weekly_commits)
     }
 }
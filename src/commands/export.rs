@@ -0,0 +1,238 @@
+use crate::app::{App, InputMode, Users};
+use crate::commands::{write_output, Context};
+use crate::error::Error;
+use crate::export::cumulative::cumulative_rows;
+use crate::export::timeline::race_timeline;
+use crate::export::video::{self, video_frames, Resolution, VideoOptions};
+use crate::export::{cast, cumulative, frames, gif, html, svg};
+use crate::ui::colors::Colors;
+use clap::ValueEnum;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// Final frame of the race as plain text
+    Text,
+    /// Every frame of the race as an asciicast v2 recording
+    Cast,
+    /// Every frame of the race as an animated image
+    Gif,
+    /// Self-contained SVG animation
+    Svg,
+    /// Standalone HTML page replaying the race
+    Html,
+    /// Weekly and cumulative commits with the rank of every author and week
+    Csv,
+    /// Cumulative commits with a row per week and a column per author
+    CsvWide,
+    /// Weekly and cumulative commits with the rank of every author and week, as JSON
+    Json,
+    /// Every frame at a constant rate as numbered PNG images, ie: 'race-00000.png'
+    Png,
+    /// Every frame at a constant rate as numbered PPM images
+    Ppm,
+    /// Every frame at a constant rate as an uncompressed YUV4MPEG2 video
+    Y4m,
+}
+
+/// Racers on screen in the vector formats when the number of users is automatic
+const AUTO_USERS: usize = 10;
+
+impl ExportFormat {
+    /// Format matching the extension of the output, ie: 'race.cast'
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        ExportFormat::value_variants()
+            .iter()
+            .find(|format| {
+                format
+                    .to_possible_value()
+                    .is_some_and(|value| value.matches(extension.as_str(), true))
+            })
+            .copied()
+    }
+}
+
+pub struct ExportOptions {
+    pub format: Option<ExportFormat>,
+    pub users: Users,
+    pub width: u16,
+    pub height: u16,
+    pub tick_rate: Duration,
+    pub total_ticks: u32,
+    pub fps: u32,
+    pub resolution: Option<Resolution>,
+}
+
+/// Renders the race into a file without a terminal
+pub fn run(
+    context: &Context,
+    repository: Option<&str>,
+    output: &str,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let format = options
+        .format
+        .or_else(|| ExportFormat::from_path(output))
+        .unwrap_or(ExportFormat::Text);
+    // the big bars are only drawn with colours, a text file needs the compact ones
+    let compact = format == ExportFormat::Text;
+    let mut app = App::new(context.blacklist.clone(), options.users, compact);
+    app.set_theme(context.theme.theme(), context.colors.clone());
+    app.set_contributors(context.contributors(repository)?)?;
+    app.input_mode = InputMode::Normal;
+
+    let commits = app
+        .commits
+        .clone()
+        .ok_or_else(|| Error::EmptyData("no contributors were loaded".to_string()))?;
+
+    let (width, height) = (options.width, options.height);
+    let content = match format {
+        ExportFormat::Text => {
+            frames::to_text(&frames::final_frame(&mut app, width, height)).into_bytes()
+        }
+        ExportFormat::Cast => cast::cast(
+            &mut app,
+            width,
+            height,
+            options.tick_rate,
+            options.total_ticks,
+        )
+        .into_bytes(),
+        ExportFormat::Gif => gif::gif(
+            &mut app,
+            width,
+            height,
+            options.tick_rate,
+            options.total_ticks,
+            options.fps,
+        )?,
+        ExportFormat::Svg | ExportFormat::Html => {
+            let users = match options.users {
+                Users::Auto => AUTO_USERS,
+                Users::Count(count) => count,
+            };
+            let frames = race_timeline(&commits, users, options.total_ticks as usize);
+            let duration = options.tick_rate * options.total_ticks;
+            // the page and the image are not terminals, they keep the colours of the racers
+            let mut colors = Colors::new(context.colors.clone());
+            if format == ExportFormat::Svg {
+                svg::svg(&frames, users, duration, &mut colors).into_bytes()
+            } else {
+                html::html(&frames, users, duration, &mut colors).into_bytes()
+            }
+        }
+        ExportFormat::Png | ExportFormat::Ppm | ExportFormat::Y4m => {
+            return export_video(&mut app, format, output, options);
+        }
+        ExportFormat::Csv => cumulative::long_csv(&cumulative_rows(&commits)).into_bytes(),
+        ExportFormat::CsvWide => cumulative::wide_csv(&commits).into_bytes(),
+        ExportFormat::Json => {
+            let json = serde_json::to_string_pretty(&cumulative_rows(&commits)).map_err(|e| {
+                Error::Encode {
+                    format: "JSON".to_string(),
+                    message: e.to_string(),
+                }
+            })?;
+            format!("{}\n", json).into_bytes()
+        }
+    };
+    write_output((output != "-").then_some(output), &content)
+}
+
+/// Writes the frames one by one, a video does not fit in memory
+fn export_video(
+    app: &mut App,
+    format: ExportFormat,
+    output: &str,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let video_options = VideoOptions {
+        resolution: options.resolution,
+        tick_rate: options.tick_rate,
+        total_ticks: options.total_ticks,
+        fps: options.fps,
+    };
+    let (width, height) = (options.width, options.height);
+    let write_error = |source| Error::Write {
+        path: output.to_string(),
+        source,
+    };
+
+    if format == ExportFormat::Y4m {
+        let writer: Box<dyn Write> = match output {
+            "-" => Box::new(io::stdout().lock()),
+            path => Box::new(File::create(path).map_err(write_error)?),
+        };
+        let mut writer = BufWriter::new(writer);
+        return video_frames(app, width, height, &video_options, |number, image| {
+            if number == 0 {
+                writer.write_all(video::y4m_header(image, options.fps).as_bytes())?;
+            }
+            writer.write_all(&video::y4m_frame(image))
+        })
+        .and_then(|_| writer.flush())
+        .or_else(|e| match e.kind() {
+            // the reader of the pipe is gone, ie: ffmpeg stopped early
+            io::ErrorKind::BrokenPipe => Ok(()),
+            _ => Err(write_error(e)),
+        });
+    }
+
+    if output == "-" {
+        return Err(Error::Config(
+            "numbered frames need a file name, ie: 'frames/race.png'".to_string(),
+        ));
+    }
+    video_frames(app, width, height, &video_options, |number, image| {
+        let content = match format {
+            ExportFormat::Png => video::png(image)?,
+            _ => video::ppm(image),
+        };
+        fs::write(numbered(output, number), content).map_err(write_error)
+    })
+}
+
+/// Path of a frame, the number goes before the extension: 'race.png' -> 'race-00007.png'
+fn numbered(path: &str, number: usize) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("frame");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(extension) => format!("{}-{:05}.{}", stem, number, extension),
+        None => format!("{}-{:05}", stem, number),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pick_format_from_extension() {
+        assert_eq!(
+            ExportFormat::from_path("race.cast"),
+            Some(ExportFormat::Cast)
+        );
+        assert_eq!(
+            ExportFormat::from_path("out/race.HTML"),
+            Some(ExportFormat::Html)
+        );
+        assert_eq!(
+            ExportFormat::from_path("timeline.json"),
+            Some(ExportFormat::Json)
+        );
+        assert_eq!(ExportFormat::from_path("out/race.TXT"), None);
+        assert_eq!(ExportFormat::from_path("race"), None);
+    }
+
+    #[test]
+    fn should_number_frames() {
+        assert_eq!(numbered("frames/race.png", 7), "frames/race-00007.png");
+        assert_eq!(numbered("race", 12), "race-00012");
+    }
+}
@@ -0,0 +1,11 @@
+use crate::commands::{write_output, Context};
+use crate::error::Error;
+use crate::github::contributors::serialize_contributors;
+
+/// Downloads the contributors JSON so it can be raced later with `--json-input`
+pub fn run(context: &Context, repository: &str, output: Option<&str>) -> Result<(), Error> {
+    let json = context.contributors_json(Some(repository))?;
+    // fail now instead of saving a file that can not be raced
+    serialize_contributors(json.as_str())?;
+    write_output(output, json.as_bytes())
+}
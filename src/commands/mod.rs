@@ -0,0 +1,64 @@
+use crate::error::Error;
+use crate::github::api::{contributors_url, get_contributors, ApiOptions};
+use crate::github::contributors::{apply_aliases, serialize_contributors, Contributor};
+use crate::ui::theme::ThemeName;
+use ratatui::style::Color;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+
+pub mod export;
+pub mod fetch;
+pub mod report;
+pub mod stats;
+
+/// Everything a command needs to download and filter the contributors
+pub struct Context {
+    pub agent: ureq::Agent,
+    pub api_options: ApiOptions,
+    pub json_input: Option<String>,
+    pub blacklist: Vec<String>,
+    pub aliases: HashMap<String, String>,
+    /// Colours chosen by the user, by racer name
+    pub colors: HashMap<String, Color>,
+    pub theme: ThemeName,
+}
+
+impl Context {
+    /// Raw contributors JSON of the repository, or of `--json-input` when there is none
+    pub fn contributors_json(&self, repository: Option<&str>) -> Result<String, Error> {
+        match (repository, self.json_input.as_ref()) {
+            (Some(repository), _) => {
+                let url = contributors_url(repository);
+                get_contributors(&self.agent, url.as_str(), &self.api_options)
+                    .map(|response| response.body)
+            }
+            (None, Some(path)) => fs::read_to_string(path).map_err(|source| Error::Io {
+                path: path.to_string(),
+                source,
+            }),
+            (None, None) => Err(Error::Config(
+                "pass a repository, ie: 'owner/repo', or --json-input".to_string(),
+            )),
+        }
+    }
+
+    /// Parsed contributors with the aliases applied
+    pub fn contributors(&self, repository: Option<&str>) -> Result<Vec<Contributor>, Error> {
+        let json = self.contributors_json(repository)?;
+        let contributors = serialize_contributors(json.as_str())?;
+        Ok(apply_aliases(contributors, &self.aliases))
+    }
+}
+
+/// Writes to the file, or to stdout when there is no file
+pub fn write_output(output: Option<&str>, content: &[u8]) -> Result<(), Error> {
+    let result = match output {
+        Some(path) => fs::write(path, content),
+        None => io::stdout().write_all(content),
+    };
+    result.map_err(|source| Error::Write {
+        path: output.unwrap_or("stdout").to_string(),
+        source,
+    })
+}
@@ -0,0 +1,228 @@
+use crate::commands::{write_output, Context};
+use crate::core::commits::{get_commits_per_week, rank_authors, Commits};
+use crate::core::stats::{author_stats, yearly_stats, AuthorStats, YearStats};
+use crate::error::Error;
+use crate::utils::date;
+use std::fmt::Write;
+use std::path::Path;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Characters of the activity sparkline, long histories are grouped into buckets of weeks
+const SPARKLINE_WIDTH: usize = 80;
+
+pub struct ReportOptions {
+    pub output: Option<String>,
+    pub top: usize,
+}
+
+/// Writes a Markdown report of the contributors of the repository
+pub fn run(
+    context: &Context,
+    repository: Option<&str>,
+    options: &ReportOptions,
+) -> Result<(), Error> {
+    let contributors = context.contributors(repository)?;
+    let commits = get_commits_per_week(contributors, context.blacklist.clone())?;
+    let title = match (repository, context.json_input.as_deref()) {
+        (Some(repository), _) => repository.to_string(),
+        (None, Some(path)) => Path::new(path)
+            .file_name()
+            .map_or(path.to_string(), |name| name.to_string_lossy().into_owned()),
+        (None, None) => "the repository".to_string(),
+    };
+    let report = report(&title, &commits, options.top);
+    write_output(options.output.as_deref(), report.as_bytes())
+}
+
+fn report(title: &str, commits: &Commits, top: usize) -> String {
+    let stats = author_stats(commits);
+    let years = yearly_stats(commits);
+    let weeks = commits.weekly_totals(None, u32::MAX);
+    let total: u32 = stats.iter().map(|s| s.commits).sum();
+    let week = |week: Option<&u32>| week.map_or("-".to_string(), date::convert_timestamp_to_date);
+
+    let mut report = format!("# Contributors of {}\n\n", escape(title));
+    let _ = writeln!(
+        report,
+        "- **{}** commits by **{}** contributors",
+        total,
+        stats.len()
+    );
+    let _ = writeln!(
+        report,
+        "- **{}** additions and **{}** deletions",
+        stats.iter().map(|s| s.additions as u64).sum::<u64>(),
+        stats.iter().map(|s| s.deletions as u64).sum::<u64>()
+    );
+    let _ = writeln!(
+        report,
+        "- **{}** weeks, from {} to {}, with commits in **{}** of them",
+        weeks.len(),
+        week(weeks.first().map(|(w, _)| w)),
+        week(weeks.last().map(|(w, _)| w)),
+        weeks.iter().filter(|(_, c)| *c > 0).count()
+    );
+
+    report.push_str("\n## Activity\n\n");
+    let values: Vec<u32> = weeks.iter().map(|(_, c)| *c).collect();
+    let (line, weeks_per_spark) = sparkline(&values, SPARKLINE_WIDTH);
+    let busiest = weeks
+        .iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
+    let _ = writeln!(report, "```\n{}\n```\n", line);
+    let _ = writeln!(
+        report,
+        "Commits per {}, the busiest week was {} with {} commits.",
+        match weeks_per_spark {
+            1 => "week".to_string(),
+            weeks => format!("{} weeks", weeks),
+        },
+        week(busiest.map(|(w, _)| w)),
+        busiest.map_or(0, |(_, c)| *c)
+    );
+
+    report.push_str("\n## Top contributors\n\n");
+    report.push_str(&contributors_table(&stats, top));
+
+    report.push_str("\n## Yearly leaders\n\n");
+    report.push_str(&years_table(&years));
+
+    report.push_str("\n## Newcomers\n\n");
+    for year in years.iter().filter(|year| !year.newcomers.is_empty()) {
+        let names: Vec<String> = year.newcomers.iter().map(|a| escape(a)).collect();
+        let _ = writeln!(
+            report,
+            "- **{}** ({}): {}",
+            year.year,
+            names.len(),
+            names.join(", ")
+        );
+    }
+
+    report.push_str("\n## Final standings\n\n");
+    let last = commits
+        .sorted_weeks()
+        .last()
+        .and_then(|week| commits.sum_commits.get(week));
+    let standings = last.map(rank_authors).unwrap_or_default();
+    for (rank, (author, c)) in standings.iter().take(top).enumerate() {
+        let _ = writeln!(report, "{}. {} — {} commits", rank + 1, escape(author), c);
+    }
+    report
+}
+
+fn contributors_table(stats: &[AuthorStats], top: usize) -> String {
+    let mut table = String::from(
+        "| # | Author | Commits | Share | Additions | Deletions | Active weeks | First week | Last week |\n\
+         |--:|:--|--:|--:|--:|--:|--:|:--|:--|\n",
+    );
+    let week =
+        |week: Option<u32>| week.map_or("-".to_string(), |w| date::convert_timestamp_to_date(&w));
+    for (rank, author) in stats.iter().take(top).enumerate() {
+        let _ = writeln!(
+            table,
+            "| {} | {} | {} | {:.1}% | {} | {} | {} | {} | {} |",
+            rank + 1,
+            escape(&author.author),
+            author.commits,
+            author.share,
+            author.additions,
+            author.deletions,
+            author.active_weeks,
+            week(author.first_week),
+            week(author.last_week)
+        );
+    }
+    table
+}
+
+fn years_table(years: &[YearStats]) -> String {
+    let mut table = String::from(
+        "| Year | Commits | Contributors | Leader | Leader commits |\n|:--|--:|--:|:--|--:|\n",
+    );
+    for year in years {
+        let (leader, commits) = match &year.leader {
+            Some((author, commits)) => (escape(author), commits.to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let _ = writeln!(
+            table,
+            "| {} | {} | {} | {} | {} |",
+            year.year, year.commits, year.contributors, leader, commits
+        );
+    }
+    table
+}
+
+/// Bars of the values, at most `width` characters. Values are summed in buckets when
+/// there are too many, the size of the buckets is returned with the line
+fn sparkline(values: &[u32], width: usize) -> (String, usize) {
+    let bucket = values.len().div_ceil(width.max(1)).max(1);
+    let sums: Vec<u32> = values.chunks(bucket).map(|c| c.iter().sum()).collect();
+    let max = sums.iter().copied().max().unwrap_or(0).max(1);
+    let line = sums
+        .iter()
+        .map(|sum| match sum {
+            0 => ' ',
+            // any activity gets at least the lowest bar
+            sum => SPARKS[((*sum as usize * SPARKS.len()).div_ceil(max as usize) - 1).min(7)],
+        })
+        .collect();
+    (line, bucket)
+}
+
+/// Logins are safe, but aliases can contain characters that Markdown would interpret
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::contributors::contributor;
+
+    #[test]
+    fn should_draw_sparklines() {
+        assert_eq!(sparkline(&[0, 1, 4, 8], 10), (" ▁▄█".to_string(), 1));
+        assert_eq!(sparkline(&[1, 1, 0, 0, 2, 2], 3), ("▄ █".to_string(), 2));
+        assert_eq!(sparkline(&[], 3), (String::new(), 1));
+    }
+
+    #[test]
+    fn should_write_markdown_report() {
+        let commits = get_commits_per_week(
+            vec![
+                contributor("octocat", &[0, 1, 2, 0, 1]),
+                contributor("hu|bot", &[6, 0, 0, 0, 0]),
+            ],
+            vec![],
+        )
+        .unwrap();
+        let report = report("owner/repo", &commits, 1);
+
+        assert!(report.starts_with(
+            "# Contributors of owner/repo\n\n- **10** commits by **2** contributors\n"
+        ));
+        assert!(report.contains(
+            "- **5** weeks, from 2013-02-17 to 2013-03-17, with commits in **4** of them\n"
+        ));
+        assert!(report.contains(
+            "```\n█▂▃ ▂\n```\n\nCommits per week, the busiest week was 2013-02-17 with 6 commits."
+        ));
+        assert!(report
+            .contains("| 1 | hu\\|bot | 6 | 60.0% | 60 | 6 | 1 | 2013-02-17 | 2013-02-17 |\n\n"));
+        assert!(report.contains("| 2013 | 10 | 2 | hu\\|bot | 6 |\n"));
+        assert!(report.contains("- **2013** (2): hu\\|bot, octocat\n"));
+        assert!(report.ends_with("## Final standings\n\n1. hu\\|bot — 6 commits\n"));
+    }
+}
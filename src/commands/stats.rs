@@ -0,0 +1,213 @@
+use crate::commands::{write_output, Context};
+use crate::core::commits::get_commits_per_week;
+use crate::core::stats::{author_stats, AuthorStats};
+use crate::error::Error;
+use crate::utils::{csv, date};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::cmp::Reverse;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum StatsFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum SortBy {
+    Commits,
+    Additions,
+    Deletions,
+    ActiveWeeks,
+    Streak,
+    Author,
+}
+
+pub struct StatsOptions {
+    pub format: StatsFormat,
+    pub sort: SortBy,
+    pub top: Option<usize>,
+}
+
+/// One line of the output, weeks are printed as dates
+#[derive(Serialize, Debug, PartialEq)]
+struct Row {
+    rank: usize,
+    author: String,
+    commits: u32,
+    additions: u32,
+    deletions: u32,
+    active_weeks: u32,
+    first_week: Option<String>,
+    last_week: Option<String>,
+    longest_streak: u32,
+    share: f64,
+}
+
+const HEADERS: [&str; 10] = [
+    "rank",
+    "author",
+    "commits",
+    "additions",
+    "deletions",
+    "active_weeks",
+    "first_week",
+    "last_week",
+    "longest_streak",
+    "share",
+];
+
+/// Prints the aggregates of every contributor
+pub fn run(
+    context: &Context,
+    repository: Option<&str>,
+    options: &StatsOptions,
+) -> Result<(), Error> {
+    let contributors = context.contributors(repository)?;
+    let commits = get_commits_per_week(contributors, context.blacklist.clone())?;
+    let rows = rows(author_stats(&commits), options.sort, options.top);
+    let output = match options.format {
+        StatsFormat::Table => table(&rows),
+        StatsFormat::Csv => csv(&rows),
+        StatsFormat::Json => {
+            let json = serde_json::to_string_pretty(&rows).map_err(|e| Error::Encode {
+                format: "JSON".to_string(),
+                message: e.to_string(),
+            })?;
+            format!("{}\n", json)
+        }
+    };
+    write_output(None, output.as_bytes())
+}
+
+fn rows(mut stats: Vec<AuthorStats>, sort: SortBy, top: Option<usize>) -> Vec<Row> {
+    // the stats come sorted by commits, a stable sort keeps that order on ties
+    match sort {
+        SortBy::Commits => {}
+        SortBy::Additions => stats.sort_by_key(|s| Reverse(s.additions)),
+        SortBy::Deletions => stats.sort_by_key(|s| Reverse(s.deletions)),
+        SortBy::ActiveWeeks => stats.sort_by_key(|s| Reverse(s.active_weeks)),
+        SortBy::Streak => stats.sort_by_key(|s| Reverse(s.longest_streak)),
+        SortBy::Author => stats.sort_by(|a, b| a.author.cmp(&b.author)),
+    }
+    let week = |week: Option<u32>| week.map(|w| date::convert_timestamp_to_date(&w));
+    stats
+        .into_iter()
+        .take(top.unwrap_or(usize::MAX))
+        .enumerate()
+        .map(|(rank, stats)| Row {
+            rank: rank + 1,
+            author: stats.author,
+            commits: stats.commits,
+            additions: stats.additions,
+            deletions: stats.deletions,
+            active_weeks: stats.active_weeks,
+            first_week: week(stats.first_week),
+            last_week: week(stats.last_week),
+            longest_streak: stats.longest_streak,
+            share: (stats.share * 100.0).round() / 100.0,
+        })
+        .collect()
+}
+
+fn fields(row: &Row) -> [String; 10] {
+    [
+        row.rank.to_string(),
+        row.author.clone(),
+        row.commits.to_string(),
+        row.additions.to_string(),
+        row.deletions.to_string(),
+        row.active_weeks.to_string(),
+        row.first_week.clone().unwrap_or_default(),
+        row.last_week.clone().unwrap_or_default(),
+        row.longest_streak.to_string(),
+        format!("{:.2}", row.share),
+    ]
+}
+
+/// Columns aligned with spaces, the author to the left and the numbers to the right
+fn table(rows: &[Row]) -> String {
+    let lines: Vec<[String; 10]> = std::iter::once(HEADERS.map(String::from))
+        .chain(rows.iter().map(fields))
+        .collect();
+    let widths: Vec<usize> = (0..HEADERS.len())
+        .map(|column| {
+            lines
+                .iter()
+                .map(|line| line[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    lines
+        .iter()
+        .map(|line| {
+            let cells: Vec<String> = line
+                .iter()
+                .zip(widths.iter())
+                .enumerate()
+                .map(|(column, (cell, width))| match column {
+                    1 => format!("{:<width$}", cell),
+                    _ => format!("{:>width$}", cell),
+                })
+                .collect();
+            format!("{}\n", cells.join("  ").trim_end())
+        })
+        .collect()
+}
+
+fn csv(rows: &[Row]) -> String {
+    std::iter::once(HEADERS.map(String::from))
+        .chain(rows.iter().map(fields))
+        .map(|line| csv::line(&line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(author: &str, commits: u32, additions: u32) -> AuthorStats {
+        AuthorStats {
+            author: author.to_string(),
+            commits,
+            additions,
+            deletions: 1,
+            active_weeks: 2,
+            first_week: Some(1361059200),
+            last_week: None,
+            longest_streak: 1,
+            share: 100.0 / 3.0,
+        }
+    }
+
+    fn sample() -> Vec<AuthorStats> {
+        vec![stats("hubot", 10, 5), stats("octo,cat", 3, 50)]
+    }
+
+    #[test]
+    fn should_sort_and_keep_top_rows() {
+        let rows = rows(sample(), SortBy::Additions, Some(1));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].author, "octo,cat");
+        assert_eq!(rows[0].rank, 1);
+        assert_eq!(rows[0].first_week, Some("2013-02-17".to_string()));
+        assert_eq!(rows[0].share, 33.33);
+    }
+
+    #[test]
+    fn should_format_table_and_csv() {
+        let rows = rows(sample(), SortBy::Commits, None);
+        assert_eq!(
+            table(&rows),
+            "rank  author    commits  additions  deletions  active_weeks  first_week  last_week  longest_streak  share\n   \
+                1  hubot          10          5          1             2  2013-02-17                          1  33.33\n   \
+                2  octo,cat        3         50          1             2  2013-02-17                          1  33.33\n"
+        );
+        assert_eq!(
+            csv(&rows).lines().nth(2),
+            Some("2,\"octo,cat\",3,50,1,2,2013-02-17,,1,33.33")
+        );
+    }
+}
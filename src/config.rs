@@ -0,0 +1,319 @@
+use crate::app::Users;
+use crate::error::Error;
+use crate::ui::theme::ThemeName;
+use crate::Args;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, ValueEnum};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+
+const PROJECT_FILE: &str = ".gh-commit-race.toml";
+
+/// Values of a config file, every key is optional
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    pub json_input: Option<String>,
+    pub blacklist: Option<Vec<String>>,
+    pub users: Option<UsersValue>,
+    pub compact: Option<bool>,
+    pub plain: Option<bool>,
+    pub changes: Option<bool>,
+    pub narrate: Option<bool>,
+    pub tick_rate: Option<u64>,
+    pub race_duration: Option<u64>,
+    pub timeout: Option<u64>,
+    pub retries: Option<u32>,
+    pub proxy: Option<String>,
+    pub ca_file: Option<String>,
+    pub aliases: Option<BTreeMap<String, String>>,
+    pub colors: Option<BTreeMap<String, String>>,
+    pub theme: Option<String>,
+}
+
+/// `users` can be written as `users = 10` or `users = "auto"`
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum UsersValue {
+    Count(usize),
+    Text(String),
+}
+
+/// Where the effective value of every option comes from
+pub type Sources = Vec<(&'static str, String)>;
+
+/// Global config file first, then the project one so it wins
+pub fn config_files() -> Vec<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from));
+    let mut files = Vec::new();
+    if let Some(config_dir) = config_dir {
+        files.push(config_dir.join("gh-commit-race").join("config.toml"));
+    }
+    files.push(PathBuf::from(PROJECT_FILE));
+    files
+}
+
+/// Reads the existing config files, missing ones are skipped
+pub fn load(files: &[PathBuf]) -> Result<Vec<(PathBuf, FileConfig)>, Error> {
+    let mut configs = Vec::new();
+    for file in files.iter().filter(|file| file.is_file()) {
+        let content = fs::read_to_string(file).map_err(|source| Error::ConfigFile {
+            path: file.display().to_string(),
+            source,
+        })?;
+        let config = toml::from_str(content.as_str())
+            .map_err(|e| Error::Config(format!("{}: {}", file.display(), e.message())))?;
+        configs.push((file.clone(), config));
+    }
+    Ok(configs)
+}
+
+/// Fills the arguments not given in the command line with the config files
+pub fn apply(
+    args: &mut Args,
+    matches: &ArgMatches,
+    configs: &[(PathBuf, FileConfig)],
+) -> Result<Sources, Error> {
+    let mut sources = Sources::new();
+
+    macro_rules! merge {
+        ($field:ident, $convert:expr) => {{
+            let mut source = "default".to_string();
+            if matches.value_source(stringify!($field)) == Some(ValueSource::CommandLine) {
+                source = "command line".to_string();
+            } else {
+                for (file, config) in configs {
+                    if let Some(value) = config.$field.as_ref() {
+                        args.$field = $convert(value)?;
+                        source = file.display().to_string();
+                    }
+                }
+            }
+            sources.push((stringify!($field), source));
+        }};
+    }
+    let some = |value: &String| Ok::<_, Error>(Some(value.clone()));
+    let copy = |value: &u64| Ok::<_, Error>(*value);
+
+    merge!(json_input, some);
+    merge!(blacklist, |value: &Vec<String>| Ok::<_, Error>(
+        value.clone()
+    ));
+    merge!(users, |value: &UsersValue| match value {
+        UsersValue::Count(count) => count.to_string().parse::<Users>(),
+        UsersValue::Text(text) => text.parse::<Users>(),
+    }
+    .map_err(|e| Error::Config(format!("users: {}", e))));
+    let flag = |value: &bool| Ok::<_, Error>(*value);
+    merge!(compact, flag);
+    merge!(plain, flag);
+    merge!(changes, flag);
+    merge!(narrate, flag);
+    merge!(tick_rate, copy);
+    merge!(race_duration, copy);
+    merge!(timeout, copy);
+    merge!(retries, |value: &u32| Ok::<_, Error>(*value));
+    merge!(proxy, some);
+    merge!(ca_file, some);
+    merge!(aliases, |value: &BTreeMap<String, String>| Ok::<_, Error>(
+        value.clone().into_iter().collect()
+    ));
+    merge!(colors, |value: &BTreeMap<String, String>| value
+        .iter()
+        .map(|(login, color)| color
+            .parse()
+            .map(|color| (login.clone(), color))
+            .map_err(|_| Error::Config(format!("colors: '{}' is not a colour", color))))
+        .collect::<Result<Vec<_>, Error>>());
+    merge!(theme, |value: &String| ThemeName::from_str(value, true)
+        .map_err(|_| Error::Config(format!(
+            "theme: '{}' is not a theme",
+            value
+        ))));
+
+    Ok(sources)
+}
+
+/// Without a theme chosen by the user, a non-empty `NO_COLOR` turns the colours off,
+/// see https://no-color.org
+pub fn apply_no_color(args: &mut Args, sources: &mut Sources, no_color: Option<OsString>) {
+    if no_color.is_none_or(|value| value.is_empty()) {
+        return;
+    }
+    if let Some((_, source)) = sources.iter_mut().find(|(name, _)| *name == "theme") {
+        if source == "default" {
+            args.theme = ThemeName::Monochrome;
+            *source = "NO_COLOR".to_string();
+        }
+    }
+}
+
+/// Effective configuration in TOML syntax, with the source of each value as a comment
+pub fn show(args: &Args, sources: &Sources) -> String {
+    let quote = |value: &str| format!("{:?}", value);
+    let optional = |value: &Option<String>| value.as_deref().map(quote);
+    let list = |values: &[String]| {
+        let values: Vec<String> = values.iter().map(|v| quote(v)).collect();
+        format!("[{}]", values.join(", "))
+    };
+    let table = |entries: &[String]| {
+        if entries.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{ {} }}", entries.join(", "))
+        }
+    };
+    let theme = args
+        .theme
+        .to_possible_value()
+        .map(|value| quote(value.get_name()));
+    let users = match args.users {
+        Users::Auto => quote("auto"),
+        Users::Count(count) => count.to_string(),
+    };
+    let aliases: Vec<String> = args
+        .aliases
+        .iter()
+        .map(|(login, alias)| format!("{} = {}", quote(login), quote(alias)))
+        .collect();
+    let colors: Vec<String> = args
+        .colors
+        .iter()
+        .map(|(login, color)| format!("{} = {}", quote(login), quote(&color.to_string())))
+        .collect();
+
+    let values: Vec<(&str, Option<String>)> = vec![
+        ("json_input", optional(&args.json_input)),
+        ("blacklist", Some(list(&args.blacklist))),
+        ("users", Some(users)),
+        ("compact", Some(args.compact.to_string())),
+        ("plain", Some(args.plain.to_string())),
+        ("changes", Some(args.changes.to_string())),
+        ("narrate", Some(args.narrate.to_string())),
+        ("tick_rate", Some(args.tick_rate.to_string())),
+        ("race_duration", Some(args.race_duration.to_string())),
+        ("timeout", Some(args.timeout.to_string())),
+        ("retries", Some(args.retries.to_string())),
+        ("proxy", optional(&args.proxy)),
+        ("ca_file", optional(&args.ca_file)),
+        ("aliases", Some(table(&aliases))),
+        ("colors", Some(table(&colors))),
+        ("theme", theme),
+    ];
+
+    values
+        .into_iter()
+        .map(|(key, value)| {
+            let source = sources
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map_or("default", |(_, source)| source.as_str());
+            match value {
+                Some(value) => format!("{} = {} # {}\n", key, value, source),
+                None => format!("# {} is not set\n", key),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches};
+
+    fn parse(cli: &[&str]) -> (Args, ArgMatches) {
+        let matches = Args::command().try_get_matches_from(cli).unwrap();
+        (Args::from_arg_matches(&matches).unwrap(), matches)
+    }
+
+    fn config(content: &str) -> FileConfig {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn should_merge_config_files_and_let_command_line_win() {
+        let global = config(
+            r#"
+            users = "auto"
+            tick_rate = 50
+            blacklist = ["dependabot"]
+            "#,
+        );
+        let project = config(
+            r##"
+            tick_rate = 20
+            [aliases]
+            octocat-work = "octocat"
+            [colors]
+            octocat = "#ff8800"
+            "##,
+        );
+        let configs = vec![
+            (PathBuf::from("global.toml"), global),
+            (PathBuf::from("project.toml"), project),
+        ];
+        let (mut args, matches) = parse(&["gh-commit-race", "--blacklist", "renovate"]);
+
+        let sources = apply(&mut args, &matches, &configs).unwrap();
+        assert_eq!(args.users, Users::Auto);
+        assert_eq!(args.tick_rate, 20);
+        assert_eq!(args.blacklist, vec!["renovate".to_string()]);
+        assert_eq!(args.race_duration, 30);
+        assert_eq!(
+            args.aliases,
+            vec![("octocat-work".to_string(), "octocat".to_string())]
+        );
+
+        let shown = show(&args, &sources);
+        assert!(shown.contains("users = \"auto\" # global.toml\n"));
+        assert!(shown.contains("tick_rate = 20 # project.toml\n"));
+        assert!(shown.contains("blacklist = [\"renovate\"] # command line\n"));
+        assert!(shown.contains("race_duration = 30 # default\n"));
+        assert!(shown.contains("colors = { \"octocat\" = \"#FF8800\" } # project.toml\n"));
+    }
+
+    #[test]
+    fn should_turn_colours_off_with_no_color() {
+        let configs = vec![(PathBuf::from("config.toml"), config("theme = \"light\""))];
+        let (mut args, matches) = parse(&["gh-commit-race"]);
+        let mut sources = apply(&mut args, &matches, &configs).unwrap();
+        apply_no_color(&mut args, &mut sources, Some("1".into()));
+        assert_eq!(args.theme, ThemeName::Light);
+
+        let (mut args, matches) = parse(&["gh-commit-race"]);
+        let mut sources = apply(&mut args, &matches, &[]).unwrap();
+        apply_no_color(&mut args, &mut sources, Some("".into()));
+        assert_eq!(args.theme, ThemeName::Dark);
+        apply_no_color(&mut args, &mut sources, Some("1".into()));
+        assert_eq!(args.theme, ThemeName::Monochrome);
+        assert!(show(&args, &sources).contains("theme = \"monochrome\" # NO_COLOR\n"));
+    }
+
+    #[test]
+    fn should_reject_invalid_values() {
+        let configs = vec![(PathBuf::from("config.toml"), config("users = 0"))];
+        let (mut args, matches) = parse(&["gh-commit-race"]);
+        assert!(matches!(
+            apply(&mut args, &matches, &configs),
+            Err(Error::Config(_))
+        ));
+        assert!(toml::from_str::<FileConfig>("unknown = 1").is_err());
+
+        let configs = vec![(
+            PathBuf::from("config.toml"),
+            config("colors = { octocat = \"#ff88\" }"),
+        )];
+        assert!(matches!(
+            apply(&mut args, &matches, &configs),
+            Err(Error::Config(_))
+        ));
+    }
+}
@@ -1,4 +1,6 @@
+use crate::error::Error;
 use crate::github::contributors::Contributor;
+use crate::utils::date;
 use std::collections::HashMap;
 
 #[derive(Clone)]
@@ -12,7 +14,9 @@ pub struct Commits {
 
 #[derive(Clone)]
 pub struct WeeklyCommits {
-    authors: HashMap<String, u32>,
+    pub authors: HashMap<String, u32>,
+    pub additions: HashMap<String, u32>,
+    pub deletions: HashMap<String, u32>,
 }
 
 #[derive(Clone)]
@@ -20,6 +24,15 @@ pub struct SumWeeklyCommits {
     pub authors: HashMap<String, u32>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorWeek {
+    pub week: u32,
+    pub commits: u32,
+    pub additions: u32,
+    pub deletions: u32,
+    pub total: u32,
+}
+
 impl Commits {
     pub fn new() -> Self {
         Commits {
@@ -30,9 +43,138 @@ impl Commits {
             sum_commits: HashMap::new(),
         }
     }
+
+    pub fn sorted_weeks(&self) -> Vec<u32> {
+        let mut weeks: Vec<u32> = self.sum_commits.keys().copied().collect();
+        weeks.sort();
+        weeks
+    }
+
+    /// Weekly activity of a single author, up to and including `until_week`
+    pub fn author_weeks(&self, author: &str, until_week: u32) -> Vec<AuthorWeek> {
+        self.sorted_weeks()
+            .into_iter()
+            .take_while(|week| *week <= until_week)
+            .map(|week| {
+                let weekly = self.weekly_commits.get(&week);
+                let value = |map: Option<&HashMap<String, u32>>| {
+                    map.and_then(|m| m.get(author)).copied().unwrap_or(0)
+                };
+                AuthorWeek {
+                    week,
+                    commits: value(weekly.map(|w| &w.authors)),
+                    additions: value(weekly.map(|w| &w.additions)),
+                    deletions: value(weekly.map(|w| &w.deletions)),
+                    total: value(self.sum_commits.get(&week).map(|s| &s.authors)),
+                }
+            })
+            .collect()
+    }
+
+    /// Commits of every week up to `until_week`, of a single author or of everyone
+    pub fn weekly_totals(&self, author: Option<&str>, until_week: u32) -> Vec<(u32, u32)> {
+        self.sorted_weeks()
+            .into_iter()
+            .take_while(|week| *week <= until_week)
+            .map(|week| {
+                let authors = self.weekly_commits.get(&week).map(|w| &w.authors);
+                let total = match (authors, author) {
+                    (Some(authors), Some(author)) => authors.get(author).copied().unwrap_or(0),
+                    (Some(authors), None) => authors.values().sum(),
+                    (None, _) => 0,
+                };
+                (week, total)
+            })
+            .collect()
+    }
+
+    /// Position (1 based) of the author in the race for every week up to `until_week`
+    pub fn rank_history(&self, author: &str, until_week: u32) -> Vec<(u32, usize)> {
+        self.sorted_weeks()
+            .into_iter()
+            .take_while(|week| *week <= until_week)
+            .filter_map(|week| {
+                let ranked = rank_authors(self.sum_commits.get(&week)?);
+                let position = ranked.iter().position(|(a, _)| a == author)?;
+                Some((week, position + 1))
+            })
+            .collect()
+    }
+
+    /// Ranking of the authors at the last week of every month up to `until_week`.
+    /// With a `window` only the commits of the last `window` weeks are counted,
+    /// otherwise the cumulative totals are used
+    pub fn monthly_rankings(
+        &self,
+        until_week: u32,
+        window: Option<usize>,
+    ) -> Vec<(u32, Vec<(String, u32)>)> {
+        let weeks: Vec<u32> = self
+            .sorted_weeks()
+            .into_iter()
+            .take_while(|week| *week <= until_week)
+            .collect();
+        let mut rankings = Vec::new();
+        let mut rolling = SumWeeklyCommits {
+            authors: HashMap::new(),
+        };
+        for (i, week) in weeks.iter().enumerate() {
+            if let Some(window) = window {
+                self.add_weekly_commits(&mut rolling, *week, true);
+                if i >= window {
+                    self.add_weekly_commits(&mut rolling, weeks[i - window], false);
+                }
+            }
+            let is_last_of_month = weeks
+                .get(i + 1)
+                .is_none_or(|next| date::year_month(next) != date::year_month(week));
+            if !is_last_of_month {
+                continue;
+            }
+            let totals = match window {
+                Some(_) => Some(&rolling),
+                None => self.sum_commits.get(week),
+            };
+            if let Some(totals) = totals {
+                let ranked = rank_authors(totals)
+                    .into_iter()
+                    .filter(|(_, c)| *c > 0)
+                    .collect();
+                rankings.push((*week, ranked));
+            }
+        }
+        rankings
+    }
+
+    fn add_weekly_commits(&self, totals: &mut SumWeeklyCommits, week: u32, add: bool) {
+        if let Some(weekly) = self.weekly_commits.get(&week) {
+            for (author, c) in weekly.authors.iter() {
+                let total = totals.authors.entry(author.clone()).or_insert(0);
+                *total = if add {
+                    *total + c
+                } else {
+                    total.saturating_sub(*c)
+                };
+            }
+        }
+    }
+}
+
+/// Authors sorted by their commits, ties are broken by login so the order is stable
+pub fn rank_authors(sum_weekly_commits: &SumWeeklyCommits) -> Vec<(String, u32)> {
+    let mut sorted_authors: Vec<(String, u32)> = sum_weekly_commits
+        .authors
+        .iter()
+        .map(|(a, c)| (a.to_string(), *c))
+        .collect();
+    sorted_authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sorted_authors
 }
 
-pub fn get_commits_per_week(contributors: Vec<Contributor>, blacklist: Vec<String>) -> Commits {
+pub fn get_commits_per_week(
+    contributors: Vec<Contributor>,
+    blacklist: Vec<String>,
+) -> Result<Commits, Error> {
     let mut commits = Commits::new();
 
     for contributor in contributors {
@@ -42,38 +184,29 @@ pub fn get_commits_per_week(contributors: Vec<Contributor>, blacklist: Vec<Strin
 
         let mut weeks = contributor.weeks;
         weeks.sort_by_key(|w| w.w);
-        commits.first_week = weeks.first().unwrap().w;
-        commits.last_week = weeks.last().unwrap().w;
+        let (Some(first_week), Some(last_week)) = (weeks.first(), weeks.last()) else {
+            continue;
+        };
+        commits.first_week = first_week.w;
+        commits.last_week = last_week.w;
         commits.total_weeks = weeks.len() as u32;
         let author = contributor.author.login;
 
-        for (i, week) in weeks.iter().enumerate() {
-            commits
+        let mut sum_commit = 0;
+        for week in weeks.iter() {
+            let weekly = commits
                 .weekly_commits
                 .entry(week.w)
                 .or_insert_with(|| WeeklyCommits {
                     authors: HashMap::new(),
-                })
-                .authors
-                .entry(author.clone())
-                .and_modify(|c| *c = week.c)
-                .or_insert(week.c);
-
-            let sum_commit = if i == 0 {
-                weeks[i].c
-            } else {
-                let current_week_commits = weeks[i].c;
-
-                let previous_week_commits = commits
-                    .sum_commits
-                    .get(&weeks[i - 1].w)
-                    .unwrap()
-                    .authors
-                    .get(&author)
-                    .unwrap();
-
-                current_week_commits + previous_week_commits
-            };
+                    additions: HashMap::new(),
+                    deletions: HashMap::new(),
+                });
+            weekly.authors.insert(author.clone(), week.c);
+            weekly.additions.insert(author.clone(), week.a);
+            weekly.deletions.insert(author.clone(), week.d);
+
+            sum_commit += week.c;
             commits
                 .sum_commits
                 .entry(week.w)
@@ -87,7 +220,12 @@ pub fn get_commits_per_week(contributors: Vec<Contributor>, blacklist: Vec<Strin
         }
     }
 
-    commits
+    if commits.sum_commits.is_empty() {
+        return Err(Error::EmptyData(
+            "no contributor has weekly data after applying the blacklist".to_string(),
+        ));
+    }
+    Ok(commits)
 }
 
 #[cfg(test)]
@@ -152,7 +290,7 @@ mod tests {
             },
         ];
 
-        let commits = get_commits_per_week(contributors, Vec::new());
+        let commits = get_commits_per_week(contributors, Vec::new()).unwrap();
         assert_eq!(commits.weekly_commits.len(), 3);
         assert_eq!(commits.sum_commits.len(), 3);
         assert_eq!(commits.first_week, 1361059200);
@@ -209,4 +347,137 @@ mod tests {
             &5
         );
     }
+
+    #[test]
+    fn should_return_author_weeks_and_rank_history() {
+        let week = |w: u32, a: u32, d: u32, c: u32| Week { w, a, d, c };
+        let contributors = vec![
+            Contributor {
+                total: 3,
+                weeks: vec![week(1361059200, 10, 2, 1), week(1361664000, 5, 1, 2)],
+                author: Author {
+                    login: "octocat".to_string(),
+                },
+            },
+            Contributor {
+                total: 2,
+                weeks: vec![week(1361059200, 20, 0, 2), week(1361664000, 0, 0, 0)],
+                author: Author {
+                    login: "octobot".to_string(),
+                },
+            },
+        ];
+
+        let commits = get_commits_per_week(contributors, Vec::new()).unwrap();
+        assert_eq!(
+            commits.weekly_totals(None, 1361664000),
+            vec![(1361059200, 3), (1361664000, 2)]
+        );
+        assert_eq!(
+            commits.weekly_totals(Some("octobot"), 1361059200),
+            vec![(1361059200, 2)]
+        );
+        let octocat = commits.author_weeks("octocat", 1361664000);
+        assert_eq!(
+            octocat,
+            vec![
+                AuthorWeek {
+                    week: 1361059200,
+                    commits: 1,
+                    additions: 10,
+                    deletions: 2,
+                    total: 1,
+                },
+                AuthorWeek {
+                    week: 1361664000,
+                    commits: 2,
+                    additions: 5,
+                    deletions: 1,
+                    total: 3,
+                },
+            ]
+        );
+        assert_eq!(commits.author_weeks("octocat", 1361059200).len(), 1);
+        assert_eq!(
+            commits.rank_history("octocat", 1361664000),
+            vec![(1361059200, 2), (1361664000, 1)]
+        );
+    }
+
+    #[test]
+    fn should_return_monthly_rankings() {
+        let week = |w: u32, c: u32| Week { w, a: 0, d: 0, c };
+        // 2013-02-17, 2013-02-24 and 2013-03-03
+        let contributors = vec![
+            Contributor {
+                total: 5,
+                weeks: vec![
+                    week(1361059200, 5),
+                    week(1361664000, 0),
+                    week(1362268800, 0),
+                ],
+                author: Author {
+                    login: "octocat".to_string(),
+                },
+            },
+            Contributor {
+                total: 3,
+                weeks: vec![
+                    week(1361059200, 0),
+                    week(1361664000, 1),
+                    week(1362268800, 2),
+                ],
+                author: Author {
+                    login: "octobot".to_string(),
+                },
+            },
+        ];
+
+        let commits = get_commits_per_week(contributors, Vec::new()).unwrap();
+        let cumulative = commits.monthly_rankings(1362268800, None);
+        assert_eq!(cumulative.len(), 2);
+        assert_eq!(cumulative[0].0, 1361664000);
+        assert_eq!(
+            cumulative[0].1,
+            vec![("octocat".to_string(), 5), ("octobot".to_string(), 1)]
+        );
+        assert_eq!(cumulative[1].1[0], ("octocat".to_string(), 5));
+
+        let rolling = commits.monthly_rankings(1362268800, Some(2));
+        assert_eq!(rolling[1].1, vec![("octobot".to_string(), 3)]);
+    }
+
+    #[test]
+    fn should_skip_contributors_without_weeks() {
+        let contributors = vec![
+            Contributor {
+                total: 0,
+                weeks: Vec::new(),
+                author: Author {
+                    login: "ghost".to_string(),
+                },
+            },
+            Contributor {
+                total: 1,
+                weeks: vec![Week {
+                    w: 1361059200,
+                    a: 0,
+                    d: 0,
+                    c: 1,
+                }],
+                author: Author {
+                    login: "octocat".to_string(),
+                },
+            },
+        ];
+
+        let commits = get_commits_per_week(contributors.clone(), Vec::new()).unwrap();
+        assert_eq!(commits.total_weeks, 1);
+        assert!(!commits.sum_commits[&1361059200]
+            .authors
+            .contains_key("ghost"));
+
+        let error = get_commits_per_week(contributors, vec!["octocat".to_string()]);
+        assert!(matches!(error, Err(Error::EmptyData(_))));
+    }
 }
@@ -1 +1,2 @@
 pub mod commits;
+pub mod stats;
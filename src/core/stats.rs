@@ -0,0 +1,200 @@
+use crate::core::commits::Commits;
+use crate::utils::date;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Totals of a contributor over the whole history of the repository
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorStats {
+    pub author: String,
+    pub commits: u32,
+    pub additions: u32,
+    pub deletions: u32,
+    pub active_weeks: u32,
+    pub first_week: Option<u32>,
+    pub last_week: Option<u32>,
+    pub longest_streak: u32,
+    /// Percentage of all the commits of the repository
+    pub share: f64,
+}
+
+/// Aggregates of every author, sorted by commits like the race
+pub fn author_stats(commits: &Commits) -> Vec<AuthorStats> {
+    let authors: BTreeSet<&String> = commits
+        .weekly_commits
+        .values()
+        .flat_map(|weekly| weekly.authors.keys())
+        .collect();
+    let mut stats: Vec<AuthorStats> = authors
+        .into_iter()
+        .map(|author| {
+            let weeks = commits.author_weeks(author, u32::MAX);
+            let active: Vec<u32> = weeks
+                .iter()
+                .filter(|week| week.commits > 0)
+                .map(|week| week.week)
+                .collect();
+            let mut longest_streak = 0;
+            let mut streak = 0;
+            for week in weeks.iter() {
+                streak = if week.commits > 0 { streak + 1 } else { 0 };
+                longest_streak = longest_streak.max(streak);
+            }
+            AuthorStats {
+                author: author.clone(),
+                commits: weeks.iter().map(|week| week.commits).sum(),
+                additions: weeks.iter().map(|week| week.additions).sum(),
+                deletions: weeks.iter().map(|week| week.deletions).sum(),
+                active_weeks: active.len() as u32,
+                first_week: active.first().copied(),
+                last_week: active.last().copied(),
+                longest_streak,
+                share: 0.0,
+            }
+        })
+        .collect();
+
+    let total: u32 = stats.iter().map(|s| s.commits).sum();
+    for author in stats.iter_mut() {
+        author.share = match total {
+            0 => 0.0,
+            total => author.commits as f64 * 100.0 / total as f64,
+        };
+    }
+    stats.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    stats
+}
+
+/// Activity of the repository during a calendar year
+#[derive(Clone, Debug, PartialEq)]
+pub struct YearStats {
+    pub year: i32,
+    pub commits: u32,
+    /// Authors with at least a commit during the year
+    pub contributors: u32,
+    /// Author with the most commits of the year and their commits
+    pub leader: Option<(String, u32)>,
+    /// Authors whose first commit is in this year, sorted by login
+    pub newcomers: Vec<String>,
+}
+
+/// Aggregates of every year of the history, oldest first
+pub fn yearly_stats(commits: &Commits) -> Vec<YearStats> {
+    let mut years: BTreeMap<i32, HashMap<&String, u32>> = BTreeMap::new();
+    for week in commits.sorted_weeks() {
+        let authors = years.entry(date::year_month(&week).0).or_default();
+        if let Some(weekly) = commits.weekly_commits.get(&week) {
+            for (author, c) in weekly.authors.iter().filter(|(_, c)| **c > 0) {
+                *authors.entry(author).or_insert(0) += c;
+            }
+        }
+    }
+
+    let mut seen: BTreeSet<&String> = BTreeSet::new();
+    years
+        .into_iter()
+        .map(|(year, authors)| {
+            let mut newcomers: Vec<String> = authors
+                .keys()
+                .filter(|author| !seen.contains(*author))
+                .map(|author| author.to_string())
+                .collect();
+            newcomers.sort();
+            seen.extend(authors.keys());
+            let leader = authors
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(author, c)| (author.to_string(), *c));
+            YearStats {
+                year,
+                commits: authors.values().sum(),
+                contributors: authors.len() as u32,
+                leader,
+                newcomers,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::commits::get_commits_per_week;
+    use crate::github::contributors::{contributor, Contributor, Week};
+
+    #[test]
+    fn should_aggregate_author_stats() {
+        let contributors = vec![
+            contributor("octocat", &[0, 1, 2, 0, 1]),
+            contributor("hubot", &[6, 0, 0, 0, 0]),
+        ];
+        let commits = get_commits_per_week(contributors, vec![]).unwrap();
+        let stats = author_stats(&commits);
+
+        assert_eq!(
+            stats[0],
+            AuthorStats {
+                author: "hubot".to_string(),
+                commits: 6,
+                additions: 60,
+                deletions: 6,
+                active_weeks: 1,
+                first_week: Some(1361059200),
+                last_week: Some(1361059200),
+                longest_streak: 1,
+                share: 60.0,
+            }
+        );
+        assert_eq!(stats[1].commits, 4);
+        assert_eq!(stats[1].active_weeks, 3);
+        assert_eq!(stats[1].first_week, Some(1361059200 + 604800));
+        assert_eq!(stats[1].last_week, Some(1361059200 + 4 * 604800));
+        assert_eq!(stats[1].longest_streak, 2);
+        assert_eq!(stats[1].share, 40.0);
+    }
+
+    #[test]
+    fn should_aggregate_years() {
+        // the weeks start mid December 2012, the last three are in 2013
+        let week = |i: u32| Week {
+            w: 1355616000 + i * 604800,
+            a: 0,
+            d: 0,
+            c: 0,
+        };
+        let contributors = vec![
+            Contributor {
+                weeks: vec![Week { c: 2, ..week(0) }, Week { c: 1, ..week(3) }],
+                ..contributor("octocat", &[])
+            },
+            Contributor {
+                weeks: vec![week(0), Week { c: 3, ..week(4) }],
+                ..contributor("hubot", &[])
+            },
+        ];
+        let commits = get_commits_per_week(contributors, vec![]).unwrap();
+
+        assert_eq!(
+            yearly_stats(&commits),
+            vec![
+                YearStats {
+                    year: 2012,
+                    commits: 2,
+                    contributors: 1,
+                    leader: Some(("octocat".to_string(), 2)),
+                    newcomers: vec!["octocat".to_string()],
+                },
+                YearStats {
+                    year: 2013,
+                    commits: 4,
+                    contributors: 2,
+                    leader: Some(("hubot".to_string(), 3)),
+                    newcomers: vec!["hubot".to_string()],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::utils::date;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Network {
+        url: String,
+        message: String,
+    },
+    HttpStatus {
+        url: String,
+        status: u16,
+    },
+    RateLimited {
+        url: String,
+        reset: Option<u32>,
+    },
+    Parse(serde_json::Error),
+    Encode {
+        format: String,
+        message: String,
+    },
+    EmptyData(String),
+    Config(String),
+    ConfigFile {
+        path: String,
+        source: std::io::Error,
+    },
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Network { url, message } => write!(
+                f,
+                "Could not reach {}: {}. Check your internet connection and try again.",
+                url, message
+            ),
+            Error::HttpStatus { url, status: 202 } => write!(
+                f,
+                "Github is still computing the statistics of {} (HTTP 202), try again in a few seconds.",
+                url
+            ),
+            Error::HttpStatus { url, status: 404 } => write!(
+                f,
+                "Repository not found at {} (HTTP 404). Check the name is 'owner/repo', for private repositories use --json-input.",
+                url
+            ),
+            Error::HttpStatus { url, status } => write!(
+                f,
+                "Github answered HTTP {} for {}, try again later.",
+                status, url
+            ),
+            Error::RateLimited { url, reset } => write!(
+                f,
+                "Github rate limit exceeded for {}, {}.",
+                url,
+                reset.map_or("wait a few minutes and try again".to_string(), |reset| {
+                    format!("the quota resets at {}", date::convert_timestamp_to_time(&reset))
+                })
+            ),
+            Error::Parse(e) => write!(
+                f,
+                "Could not parse the contributors data: {}. Make sure it is the response of 'contributors-data'.",
+                e
+            ),
+            Error::Encode { format, message } => {
+                write!(f, "Could not encode the {}: {}.", format, message)
+            }
+            Error::EmptyData(message) => write!(
+                f,
+                "No commits to race: {}. Check the repository and the blacklist.",
+                message
+            ),
+            Error::Config(message) => write!(f, "Invalid configuration: {}.", message),
+            Error::ConfigFile { path, source } => write!(
+                f,
+                "Could not read the config file {}: {}. Check it is a readable UTF-8 TOML file.",
+                path, source
+            ),
+            Error::Io { path, source } => write!(
+                f,
+                "Could not read {}: {}. Check the path passed to --json-input.",
+                path, source
+            ),
+            Error::Write { path, source } => write!(
+                f,
+                "Could not write {}: {}. Check the directory exists and is writable.",
+                path, source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e)
+    }
+}
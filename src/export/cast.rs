@@ -0,0 +1,160 @@
+use crate::app::App;
+use crate::export::frames::race_frames;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use serde_json::json;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Records the race as an asciicast v2 file, ie: to play it with `asciinema play`
+/// or to embed it in a web page. Every tick is an event at its real time
+pub fn cast(
+    app: &mut App,
+    width: u16,
+    height: u16,
+    tick_rate: Duration,
+    total_ticks: u32,
+) -> String {
+    // the version goes first, players read it before anything else
+    let header = format!(
+        r#"{{"version": 2, "width": {}, "height": {}, "env": {{"TERM": "xterm-256color"}}}}"#,
+        width, height
+    );
+    let mut cast = format!("{}\n", header);
+    let mut previous: Option<Buffer> = None;
+    race_frames(app, width, height, total_ticks, |tick, buffer| {
+        if previous.as_ref() == Some(buffer) {
+            return;
+        }
+        let mut output = match previous {
+            // hide the cursor and start from a blank screen
+            None => "\x1b[?25l\x1b[2J".to_string(),
+            Some(_) => String::new(),
+        };
+        output.push_str(&ansi_frame(buffer));
+        let time = (tick_rate * tick).as_secs_f64();
+        cast.push_str(&format!("{}\n", json!([time, "o", output])));
+        previous = Some(buffer.clone());
+    });
+    cast
+}
+
+/// Escape sequences that draw the whole buffer from the top left corner
+fn ansi_frame(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    let mut output = String::new();
+    for (row, cells) in buffer.content.chunks(width.max(1)).enumerate() {
+        // every row is positioned, a symbol wider than a cell can not shift the next ones
+        let _ = write!(output, "\x1b[{};1H", row + 1);
+        let mut style = None;
+        for cell in cells {
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                output.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                style = Some(cell_style);
+            }
+            output.push_str(cell.symbol());
+        }
+        output.push_str("\x1b[0m");
+    }
+    output
+}
+
+/// Select Graphic Rendition sequence of a cell style, starting from a reset
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string()];
+    let modifiers = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+    for (flag, code) in modifiers {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(color_code(fg, false));
+    codes.extend(color_code(bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let (extended, offset) = if background { (48, 10) } else { (38, 0) };
+    let basic = |code: u8| Some((code + offset).to_string());
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", extended, r, g, b)),
+        Color::Indexed(index) => Some(format!("{};5;{}", extended, index)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Users;
+    use crate::github::contributors::contributor;
+
+    #[test]
+    fn should_write_asciicast_with_tick_timings() {
+        let mut app = App::new(vec![], Users::Count(2), true);
+        app.set_contributors(vec![
+            contributor("octocat", &[1, 2, 3]),
+            contributor("hubot", &[3, 0, 0]),
+        ])
+        .unwrap();
+        app.input_mode = crate::app::InputMode::Normal;
+
+        let cast = cast(&mut app, 40, 10, Duration::from_millis(250), 3);
+        let lines: Vec<serde_json::Value> = cast
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 40);
+        assert_eq!(lines[0]["height"], 10);
+        let times: Vec<f64> = lines[1..].iter().map(|e| e[0].as_f64().unwrap()).collect();
+        assert_eq!(times, vec![0.0, 0.25, 0.5]);
+        assert_eq!(lines[1][1], "o");
+        let first = lines[1][2].as_str().unwrap();
+        assert!(first.starts_with("\x1b[?25l\x1b[2J\x1b[1;1H"));
+        assert!(first.contains("hubot"));
+    }
+
+    #[test]
+    fn should_convert_styles_to_sgr() {
+        assert_eq!(
+            sgr(Color::Reset, Color::Reset, Modifier::empty()),
+            "\x1b[0m"
+        );
+        assert_eq!(
+            sgr(Color::Rgb(1, 2, 3), Color::Blue, Modifier::BOLD),
+            "\x1b[0;1;38;2;1;2;3;44m"
+        );
+        assert_eq!(
+            sgr(Color::DarkGray, Color::Indexed(8), Modifier::empty()),
+            "\x1b[0;90;48;5;8m"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::core::commits::{rank_authors, Commits};
+use crate::utils::{csv, date};
+use serde::Serialize;
+
+/// Commits of an author at a week, with the totals of the race so far
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CumulativeRow {
+    pub week: String,
+    pub author: String,
+    pub commits: u32,
+    pub cumulative: u32,
+    pub rank: usize,
+}
+
+const HEADERS: [&str; 5] = ["week", "author", "commits", "cumulative", "rank"];
+
+/// One row per week and author, ordered by week then rank
+pub fn cumulative_rows(commits: &Commits) -> Vec<CumulativeRow> {
+    let mut rows = Vec::new();
+    for week in commits.sorted_weeks() {
+        let Some(sum) = commits.sum_commits.get(&week) else {
+            continue;
+        };
+        let weekly = commits.weekly_commits.get(&week).map(|w| &w.authors);
+        for (rank, (author, cumulative)) in rank_authors(sum).into_iter().enumerate() {
+            rows.push(CumulativeRow {
+                week: date::convert_timestamp_to_date(&week),
+                commits: weekly
+                    .and_then(|authors| authors.get(&author))
+                    .copied()
+                    .unwrap_or(0),
+                author,
+                cumulative,
+                rank: rank + 1,
+            });
+        }
+    }
+    rows
+}
+
+/// Long format, a row per week and author
+pub fn long_csv(rows: &[CumulativeRow]) -> String {
+    std::iter::once(csv::line(&HEADERS))
+        .chain(rows.iter().map(|row| {
+            csv::line(&[
+                row.week.clone(),
+                row.author.clone(),
+                row.commits.to_string(),
+                row.cumulative.to_string(),
+                row.rank.to_string(),
+            ])
+        }))
+        .collect()
+}
+
+/// Wide format, a row per week and a column of cumulative commits per author, the
+/// authors are ordered by their final rank. This is what most bar chart race tools read
+pub fn wide_csv(commits: &Commits) -> String {
+    let weeks = commits.sorted_weeks();
+    let authors: Vec<String> = weeks
+        .last()
+        .and_then(|week| commits.sum_commits.get(week))
+        .map(|sum| rank_authors(sum).into_iter().map(|(a, _)| a).collect())
+        .unwrap_or_default();
+
+    let header: Vec<&str> = std::iter::once("week")
+        .chain(authors.iter().map(String::as_str))
+        .collect();
+    let mut output = csv::line(&header);
+    for week in weeks {
+        let sum = commits.sum_commits.get(&week).map(|s| &s.authors);
+        let line: Vec<String> = std::iter::once(date::convert_timestamp_to_date(&week))
+            .chain(authors.iter().map(|author| {
+                sum.and_then(|authors| authors.get(author))
+                    .copied()
+                    .unwrap_or(0)
+                    .to_string()
+            }))
+            .collect();
+        output.push_str(&csv::line(&line));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::commits::get_commits_per_week;
+    use crate::github::contributors::contributor;
+
+    fn commits() -> Commits {
+        get_commits_per_week(
+            vec![
+                contributor("octo,cat", &[1, 0, 3]),
+                contributor("hubot", &[2, 1, 0]),
+            ],
+            vec![],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_export_long_csv() {
+        let rows = cumulative_rows(&commits());
+        assert_eq!(rows.len(), 6);
+        assert_eq!(
+            rows[4],
+            CumulativeRow {
+                week: "2013-03-03".to_string(),
+                author: "octo,cat".to_string(),
+                commits: 3,
+                cumulative: 4,
+                rank: 1,
+            }
+        );
+        assert_eq!(
+            long_csv(&rows).lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "week,author,commits,cumulative,rank",
+                "2013-02-17,hubot,2,2,1",
+                "2013-02-17,\"octo,cat\",1,1,2",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_export_wide_csv() {
+        assert_eq!(
+            wide_csv(&commits()),
+            "week,\"octo,cat\",hubot\n2013-02-17,1,2\n2013-02-24,1,3\n2013-03-03,4,3\n"
+        );
+    }
+}
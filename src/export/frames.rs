@@ -0,0 +1,52 @@
+use crate::app::App;
+use crate::ui;
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::time::Duration;
+
+/// The final standings stay on screen before the animation starts again
+pub const FINAL_FRAME_HOLD: Duration = Duration::from_secs(3);
+
+/// Draws the race headless, calling `on_frame` with the tick and the screen after
+/// every tick, the same frames the terminal shows. Without contributors the ticks do
+/// not advance, there is no frame
+pub fn race_frames(
+    app: &mut App,
+    width: u16,
+    height: u16,
+    total_ticks: u32,
+    mut on_frame: impl FnMut(u32, &Buffer),
+) {
+    if app.commits.is_none() {
+        return;
+    }
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("in memory backend");
+    while app.current_tick <= total_ticks {
+        let tick = app.current_tick;
+        app.on_tick(total_ticks);
+        terminal
+            .draw(|frame| ui::draw(frame, app))
+            .expect("in memory backend");
+        on_frame(tick, terminal.backend().buffer());
+    }
+}
+
+/// Last frame of the race, when every contributor has reached the final week
+pub fn final_frame(app: &mut App, width: u16, height: u16) -> Buffer {
+    let mut last = Buffer::empty(Default::default());
+    // a race of a single tick goes from the first week straight to the last one
+    race_frames(app, width, height, 1, |_, buffer| last = buffer.clone());
+    last
+}
+
+/// Symbols of the buffer without styles, one line per row
+pub fn to_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width.max(1))
+        .map(|row| {
+            let line: String = row.iter().map(|cell| cell.symbol()).collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
@@ -0,0 +1,141 @@
+use crate::app::App;
+use crate::error::Error;
+use crate::export::frames::{race_frames, FINAL_FRAME_HOLD};
+use crate::export::raster::{rasterize, Image, CELL_HEIGHT, CELL_WIDTH};
+use gif::{Encoder, Frame, Repeat};
+use ratatui::buffer::Buffer;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Encodes the race as an animated GIF that loops forever, at most `fps` frames per
+/// second. Frames that do not change are merged into the previous one
+pub fn gif(
+    app: &mut App,
+    width: u16,
+    height: u16,
+    tick_rate: Duration,
+    total_ticks: u32,
+    fps: u32,
+) -> Result<Vec<u8>, Error> {
+    // the dimensions of a GIF are 16 bits
+    let (pixels_wide, pixels_high) = (width as u32 * CELL_WIDTH, height as u32 * CELL_HEIGHT);
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(pixels_wide), u16::try_from(pixels_high))
+    else {
+        return Err(Error::Config(format!(
+            "a GIF of {}x{} cells is {}x{} pixels, at most {} are allowed, lower --width or --height",
+            width,
+            height,
+            pixels_wide,
+            pixels_high,
+            u16::MAX
+        )));
+    };
+    let interval = Duration::from_secs(1) / fps.max(1);
+    let mut frames: Vec<(Duration, Buffer)> = Vec::new();
+    race_frames(app, width, height, total_ticks, |tick, buffer| {
+        let time = tick_rate * tick;
+        match frames.last_mut() {
+            Some((_, last)) if last == buffer => {}
+            // too soon for a new frame, the last one shows the latest screen instead
+            Some((last_time, last)) if time < *last_time + interval => *last = buffer.clone(),
+            _ => frames.push((time, buffer.clone())),
+        }
+    });
+
+    if frames.is_empty() {
+        return Err(Error::EmptyData("the race has no frames".to_string()));
+    }
+    let encode_error = |e: gif::EncodingError| Error::Encode {
+        format: "GIF".to_string(),
+        message: e.to_string(),
+    };
+    let mut output = Vec::new();
+    {
+        let mut encoder =
+            Encoder::new(&mut output, gif_width, gif_height, &[]).map_err(encode_error)?;
+        encoder.set_repeat(Repeat::Infinite).map_err(encode_error)?;
+        for (index, (time, buffer)) in frames.iter().enumerate() {
+            let end = frames
+                .get(index + 1)
+                .map_or(*time + FINAL_FRAME_HOLD, |(next, _)| *next);
+            let mut frame = encode_frame(&rasterize(buffer));
+            // the delay is in hundredths of a second, rounding the ends avoids drifting
+            let centiseconds = |time: Duration| (time.as_millis() as f64 / 10.0).round() as u64;
+            frame.delay = (centiseconds(end) - centiseconds(*time)).min(u16::MAX as u64) as u16;
+            encoder.write_frame(&frame).map_err(encode_error)?;
+        }
+    }
+    Ok(output)
+}
+
+/// Frame with its own palette, the screen rarely has more than 256 colours so there is
+/// no need to quantize them. The size of the image was checked to fit in 16 bits
+fn encode_frame(image: &Image) -> Frame<'static> {
+    let (width, height) = (image.width as u16, image.height as u16);
+    let mut palette: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut indexes = Vec::with_capacity(image.pixels.len() / 3);
+    for pixel in image.pixels.chunks_exact(3) {
+        let color = [pixel[0], pixel[1], pixel[2]];
+        let next = palette.len();
+        let index = *palette.entry(color).or_insert(next.min(255) as u8);
+        if palette.len() > 256 {
+            return Frame::from_rgb_speed(width, height, &image.pixels, 10);
+        }
+        indexes.push(index);
+    }
+    let mut colors = vec![0; palette.len() * 3];
+    for (color, index) in palette {
+        let start = index as usize * 3;
+        colors[start..start + 3].copy_from_slice(&color);
+    }
+    Frame::from_palette_pixels(width, height, indexes, colors, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{InputMode, Users};
+    use crate::github::contributors::contributor;
+
+    #[test]
+    fn should_encode_animated_gif() {
+        let mut app = App::new(vec![], Users::Count(2), false);
+        app.set_contributors(vec![
+            contributor("octocat", &[1, 2, 3, 4]),
+            contributor("hubot", &[3, 0, 0, 0]),
+        ])
+        .unwrap();
+        app.input_mode = InputMode::Normal;
+
+        let gif = gif(&mut app, 30, 12, Duration::from_millis(100), 4, 5).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (240, 192));
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        // 5 fps keeps a frame every 200ms out of the 100ms ticks
+        assert_eq!(delays, vec![20, 300]);
+    }
+
+    #[test]
+    fn should_reject_gif_larger_than_16_bits() {
+        let mut app = App::new(vec![], Users::Count(2), false);
+        app.set_contributors(vec![contributor("octocat", &[1, 2])])
+            .unwrap();
+        app.input_mode = InputMode::Normal;
+
+        let error = gif(&mut app, 8192, 12, Duration::from_millis(100), 2, 5);
+        assert!(matches!(error, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn should_reject_race_without_frames() {
+        // nothing was loaded, there is no week to draw
+        let mut app = App::new(vec![], Users::Count(2), false);
+        app.input_mode = InputMode::Normal;
+
+        let error = gif(&mut app, 30, 12, Duration::from_millis(100), 2, 5);
+        assert!(matches!(error, Err(Error::EmptyData(_))));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::export::frames::FINAL_FRAME_HOLD;
+use crate::export::svg::escape;
+use crate::export::timeline::{racer_colors, RaceFrame};
+use crate::ui::colors::Colors;
+use crate::utils::date;
+use serde_json::json;
+use std::time::Duration;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body { margin: 0; background: #1e1e1e; color: #d4d4d4; font: 14px monospace; }
+  #race { max-width: 800px; padding: 16px; }
+  h1 { display: flex; justify-content: space-between; font-size: 18px; font-weight: normal; }
+  #bars { position: relative; overflow: hidden; }
+  .row { position: absolute; left: 0; right: 0; height: 26px; display: flex; align-items: center;
+         transition: transform var(--frame) linear, opacity var(--frame) linear; }
+  .name { width: 152px; padding-right: 8px; text-align: right; overflow: hidden; white-space: nowrap; }
+  .track { flex: 1; display: flex; align-items: center; }
+  .bar { height: 26px; border-radius: 3px; transition: width var(--frame) linear; }
+  .value { padding-left: 6px; }
+</style>
+</head>
+<body>
+<div id="race">
+  <h1><span>{title}</span><span id="date"></span></h1>
+  <div id="bars"></div>
+</div>
+<script>
+const race = {data};
+const ROW = 36;
+const bars = document.getElementById("bars");
+const date = document.getElementById("date");
+bars.style.height = race.users * ROW + "px";
+document.body.style.setProperty("--frame", race.frameMs + "ms");
+
+const rows = new Map();
+for (const [name, color] of Object.entries(race.colors)) {
+  const row = document.createElement("div");
+  row.className = "row";
+  row.innerHTML = '<span class="name"></span><span class="track"><span class="bar"></span><span class="value"></span></span>';
+  row.querySelector(".name").textContent = name;
+  row.querySelector(".name").style.color = color;
+  row.querySelector(".bar").style.background = color;
+  row.style.opacity = 0;
+  row.style.transform = `translateY(${race.users * ROW}px)`;
+  bars.appendChild(row);
+  rows.set(name, row);
+}
+
+function show(frame) {
+  date.textContent = frame.date;
+  const leader = Math.max(1, ...frame.standings.map(([, commits]) => commits));
+  const ranks = new Map(frame.standings.map(([name, commits], rank) => [name, [rank, commits]]));
+  for (const [name, row] of rows) {
+    const entry = ranks.get(name);
+    const rank = entry ? entry[0] : race.users;
+    row.style.opacity = entry ? 1 : 0;
+    row.style.transform = `translateY(${rank * ROW}px)`;
+    if (entry) {
+      row.querySelector(".bar").style.width = `calc(${entry[1] / leader} * (100% - 70px))`;
+      row.querySelector(".value").textContent = entry[1].toLocaleString();
+    }
+  }
+}
+
+let index = 0;
+function step() {
+  show(race.frames[index]);
+  const last = index === race.frames.length - 1;
+  index = last ? 0 : index + 1;
+  setTimeout(step, last ? race.holdMs : race.frameMs);
+}
+if (race.frames.length > 0) {
+  step();
+}
+</script>
+</body>
+</html>
+"#;
+
+/// Standalone page that replays the race with a small script, the data is embedded
+/// so it can be opened from disk or hosted anywhere
+pub fn html(frames: &[RaceFrame], users: usize, duration: Duration, colors: &mut Colors) -> String {
+    let colors: serde_json::Map<String, serde_json::Value> = racer_colors(frames, colors)
+        .into_iter()
+        .map(|(racer, color)| (racer, color.into()))
+        .collect();
+    let frames: Vec<serde_json::Value> = frames
+        .iter()
+        .map(|frame| {
+            json!({
+                "date": date::convert_timestamp_to_month_and_year(&frame.week),
+                "standings": frame.standings,
+            })
+        })
+        .collect();
+    let data = json!({
+        "users": users,
+        "frameMs": duration.as_millis() as usize / frames.len().max(1),
+        "holdMs": FINAL_FRAME_HOLD.as_millis() as usize,
+        "colors": colors,
+        "frames": frames,
+    });
+    // a login like '</script>' must not close the script
+    let data = data.to_string().replace("</", "<\\/");
+    PAGE.replace("{title}", &escape("Github commit race"))
+        .replace("{data}", &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+    use std::collections::HashMap;
+
+    #[test]
+    fn should_embed_race_data() {
+        let frames = vec![
+            RaceFrame {
+                week: 1361059200,
+                standings: vec![("octocat".to_string(), 2)],
+            },
+            RaceFrame {
+                week: 1361664000,
+                standings: vec![("</script>".to_string(), 3)],
+            },
+        ];
+        let mut colors = Colors::new(HashMap::from([(
+            "octocat".to_string(),
+            Color::Rgb(255, 136, 0),
+        )]));
+        let html = html(&frames, 1, Duration::from_secs(2), &mut colors);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(r#""frameMs":1000"#));
+        assert!(html.contains(r#"{"date":"Feb 2013","standings":[["octocat",2]]}"#));
+        assert!(html.contains(r##""octocat":"#ff8800""##));
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+}
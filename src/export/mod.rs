@@ -0,0 +1,9 @@
+pub mod cast;
+pub mod cumulative;
+pub mod frames;
+pub mod gif;
+pub mod html;
+pub mod raster;
+pub mod svg;
+pub mod timeline;
+pub mod video;
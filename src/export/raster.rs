@@ -0,0 +1,440 @@
+use embedded_graphics::{
+    mono_font::{
+        iso_8859_1::{FONT_8X13, FONT_8X13_BOLD},
+        MonoTextStyle,
+    },
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Baseline, Text},
+};
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use std::convert::Infallible;
+
+/// Size in pixels of a terminal cell, the font is 8x13 and centered vertically
+pub const CELL_WIDTH: u32 = 8;
+pub const CELL_HEIGHT: u32 = 16;
+const FONT_TOP: i32 = 2;
+
+pub const DEFAULT_FOREGROUND: [u8; 3] = [0xd4, 0xd4, 0xd4];
+pub const DEFAULT_BACKGROUND: [u8; 3] = [0x1e, 0x1e, 0x1e];
+
+/// RGB image, 3 bytes per pixel row by row
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: u32, height: u32) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![0; (width * height * 3) as usize],
+        }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let index = ((row * self.width + column) * 3) as usize;
+                self.pixels[index..index + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Image `factor` times bigger, every pixel becomes a square
+    pub fn scaled(&self, factor: u32) -> Image {
+        let factor = factor.max(1);
+        let mut image = Image::new(self.width * factor, self.height * factor);
+        for (index, pixel) in self.pixels.chunks_exact(3).enumerate() {
+            let (x, y) = (index as u32 % self.width, index as u32 / self.width);
+            image.fill(
+                x * factor,
+                y * factor,
+                factor,
+                factor,
+                [pixel[0], pixel[1], pixel[2]],
+            );
+        }
+        image
+    }
+
+    /// Image of exactly `width` x `height`, centered on the background and cropped
+    /// when it does not fit
+    pub fn centered(&self, width: u32, height: u32, background: [u8; 3]) -> Image {
+        let mut image = Image::new(width, height);
+        image.fill(0, 0, width, height, background);
+        let left = width.saturating_sub(self.width) / 2;
+        let top = height.saturating_sub(self.height) / 2;
+        let columns = self.width.min(width) as usize;
+        for row in 0..self.height.min(height) {
+            let source = (row * self.width) as usize * 3;
+            let target = (((top + row) * width + left) * 3) as usize;
+            image.pixels[target..target + columns * 3]
+                .copy_from_slice(&self.pixels[source..source + columns * 3]);
+        }
+        image
+    }
+}
+
+/// Lets embedded-graphics draw the glyphs of the font into the image
+struct Glyph<'a> {
+    image: &'a mut Image,
+}
+
+impl OriginDimensions for Glyph<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.image.width, self.image.height)
+    }
+}
+
+impl DrawTarget for Glyph<'_> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) {
+                if x < self.image.width && y < self.image.height {
+                    self.image
+                        .fill(x, y, 1, 1, [color.r(), color.g(), color.b()]);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draws the cells of the buffer with their colours, like a terminal would
+pub fn rasterize(buffer: &Buffer) -> Image {
+    let columns = buffer.area.width as u32;
+    let rows = buffer.area.height as u32;
+    let mut image = Image::new(columns * CELL_WIDTH, rows * CELL_HEIGHT);
+    for (index, cell) in buffer.content.iter().enumerate() {
+        let x = (index as u32 % columns.max(1)) * CELL_WIDTH;
+        let y = (index as u32 / columns.max(1)) * CELL_HEIGHT;
+        draw_cell(&mut image, cell, x, y);
+    }
+    image
+}
+
+fn draw_cell(image: &mut Image, cell: &Cell, x: u32, y: u32) {
+    let mut foreground = rgb(cell.fg, DEFAULT_FOREGROUND);
+    let mut background = rgb(cell.bg, DEFAULT_BACKGROUND);
+    if cell.modifier.contains(Modifier::REVERSED) {
+        (foreground, background) = (background, foreground);
+    }
+    image.fill(x, y, CELL_WIDTH, CELL_HEIGHT, background);
+
+    let symbol = cell.symbol();
+    let Some(character) = symbol.chars().next() else {
+        return;
+    };
+    if character == ' ' || cell.modifier.contains(Modifier::HIDDEN) {
+        return;
+    }
+    if draw_shape(image, character, x, y, foreground, background) {
+        return;
+    }
+    let font = if cell.modifier.contains(Modifier::BOLD) {
+        &FONT_8X13_BOLD
+    } else {
+        &FONT_8X13
+    };
+    let style = MonoTextStyle::new(
+        font,
+        Rgb888::new(foreground[0], foreground[1], foreground[2]),
+    );
+    let position = Point::new(x as i32, y as i32 + FONT_TOP);
+    let _ = Text::with_baseline(symbol, position, style, Baseline::Top).draw(&mut Glyph { image });
+}
+
+/// Block elements, box drawing and the markers of the charts are not part of the font,
+/// they are drawn as rectangles. Returns false for any other character
+fn draw_shape(
+    image: &mut Image,
+    character: char,
+    x: u32,
+    y: u32,
+    foreground: [u8; 3],
+    background: [u8; 3],
+) -> bool {
+    // rectangles in eighths of the cell: left, top, right, bottom
+    let mut fill = |left: u32, top: u32, right: u32, bottom: u32, color: [u8; 3]| {
+        let (x0, x1) = (left * CELL_WIDTH / 8, right * CELL_WIDTH / 8);
+        let (y0, y1) = (top * CELL_HEIGHT / 8, bottom * CELL_HEIGHT / 8);
+        image.fill(x + x0, y + y0, x1 - x0, y1 - y0, color);
+    };
+    match character {
+        '▀' => fill(0, 0, 8, 4, foreground),
+        '▁'..='█' => {
+            let eighths = character as u32 - '▀' as u32;
+            fill(0, 8 - eighths, 8, 8, foreground);
+        }
+        '▉'..='▏' => {
+            let eighths = '▏' as u32 - character as u32 + 1;
+            fill(0, 0, eighths, 8, foreground);
+        }
+        '▐' => fill(4, 0, 8, 8, foreground),
+        '░' | '▒' | '▓' => {
+            let weight = match character {
+                '░' => 1,
+                '▒' => 2,
+                _ => 3,
+            };
+            fill(0, 0, 8, 8, blend(background, foreground, weight));
+        }
+        '▔' => fill(0, 0, 8, 1, foreground),
+        '▕' => fill(7, 0, 8, 8, foreground),
+        '▖'..='▟' => {
+            // quadrants: upper left, upper right, lower left, lower right
+            let quadrants = match character {
+                '▖' => [false, false, true, false],
+                '▗' => [false, false, false, true],
+                '▘' => [true, false, false, false],
+                '▙' => [true, false, true, true],
+                '▚' => [true, false, false, true],
+                '▛' => [true, true, true, false],
+                '▜' => [true, true, false, true],
+                '▝' => [false, true, false, false],
+                '▞' => [false, true, true, false],
+                _ => [false, true, true, true],
+            };
+            for (quadrant, filled) in quadrants.iter().enumerate() {
+                if *filled {
+                    let (left, top) = ((quadrant as u32 % 2) * 4, (quadrant as u32 / 2) * 4);
+                    fill(left, top, left + 4, top + 4, foreground);
+                }
+            }
+        }
+        '■' => fill(1, 2, 7, 6, foreground),
+        '•' | '●' => fill(3, 3, 5, 5, foreground),
+        '▶' | '◀' => {
+            // a triangle made of columns that shrink towards the tip
+            for column in 0..CELL_WIDTH {
+                let distance = if character == '▶' {
+                    column
+                } else {
+                    CELL_WIDTH - 1 - column
+                };
+                let half = (CELL_HEIGHT / 2).saturating_sub(distance + 2);
+                image.fill(
+                    x + column,
+                    y + CELL_HEIGHT / 2 - half,
+                    1,
+                    half * 2,
+                    foreground,
+                );
+            }
+        }
+        '\u{2800}'..='\u{28ff}' => {
+            // braille dots: bits 0-2 and 6 are the left column, 3-5 and 7 the right one
+            let dots = character as u32 - 0x2800;
+            let positions = [
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (0, 3),
+                (1, 3),
+            ];
+            for (bit, (column, row)) in positions.iter().enumerate() {
+                if dots & (1 << bit) != 0 {
+                    let (left, top) = (1 + column * 4, row * 2);
+                    fill(left, top, left + 2, top + 1, foreground);
+                }
+            }
+        }
+        _ => match box_arms(character) {
+            Some((arms, thickness)) => draw_box(image, arms, thickness, x, y, foreground),
+            None => return false,
+        },
+    }
+    true
+}
+
+/// Arms of a box drawing character (left, right, up, down) and their thickness
+fn box_arms(character: char) -> Option<([bool; 4], u32)> {
+    let (arms, thickness) = match character {
+        '─' | '═' => ([true, true, false, false], 1),
+        '━' => ([true, true, false, false], 2),
+        '│' | '║' => ([false, false, true, true], 1),
+        '┃' => ([false, false, true, true], 2),
+        '┌' | '╭' | '╔' => ([false, true, false, true], 1),
+        '┐' | '╮' | '╗' => ([true, false, false, true], 1),
+        '└' | '╰' | '╚' => ([false, true, true, false], 1),
+        '┘' | '╯' | '╝' => ([true, false, true, false], 1),
+        '┏' => ([false, true, false, true], 2),
+        '┓' => ([true, false, false, true], 2),
+        '┗' => ([false, true, true, false], 2),
+        '┛' => ([true, false, true, false], 2),
+        '├' | '╠' => ([false, true, true, true], 1),
+        '┤' | '╣' => ([true, false, true, true], 1),
+        '┬' | '╦' => ([true, true, false, true], 1),
+        '┴' | '╩' => ([true, true, true, false], 1),
+        '┼' | '╬' => ([true, true, true, true], 1),
+        _ => return None,
+    };
+    Some((arms, thickness))
+}
+
+fn draw_box(image: &mut Image, arms: [bool; 4], thickness: u32, x: u32, y: u32, color: [u8; 3]) {
+    let (center_x, center_y) = (x + CELL_WIDTH / 2, y + CELL_HEIGHT / 2);
+    let [left, right, up, down] = arms;
+    if left {
+        image.fill(x, center_y, CELL_WIDTH / 2 + thickness, thickness, color);
+    }
+    if right {
+        image.fill(center_x, center_y, CELL_WIDTH / 2, thickness, color);
+    }
+    if up {
+        image.fill(center_x, y, thickness, CELL_HEIGHT / 2 + thickness, color);
+    }
+    if down {
+        image.fill(center_x, center_y, thickness, CELL_HEIGHT / 2, color);
+    }
+}
+
+/// Mix of two colours, `weight` quarters of the second one
+fn blend(first: [u8; 3], second: [u8; 3], weight: u32) -> [u8; 3] {
+    let mix = |a: u8, b: u8| ((a as u32 * (4 - weight) + b as u32 * weight) / 4) as u8;
+    [
+        mix(first[0], second[0]),
+        mix(first[1], second[1]),
+        mix(first[2], second[2]),
+    ]
+}
+
+/// Colour of the terminal palette, `Reset` is the default of the terminal
+pub fn rgb(color: Color, default: [u8; 3]) -> [u8; 3] {
+    match color {
+        Color::Reset => default,
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(index) => indexed(index),
+        Color::Black => indexed(0),
+        Color::Red => indexed(1),
+        Color::Green => indexed(2),
+        Color::Yellow => indexed(3),
+        Color::Blue => indexed(4),
+        Color::Magenta => indexed(5),
+        Color::Cyan => indexed(6),
+        Color::Gray => indexed(7),
+        Color::DarkGray => indexed(8),
+        Color::LightRed => indexed(9),
+        Color::LightGreen => indexed(10),
+        Color::LightYellow => indexed(11),
+        Color::LightBlue => indexed(12),
+        Color::LightMagenta => indexed(13),
+        Color::LightCyan => indexed(14),
+        Color::White => indexed(15),
+    }
+}
+
+/// xterm 256 colours: 16 system colours, a 6x6x6 cube and 24 grays. The system colours
+/// are the ones of the VS Code terminal, easier to read on a dark background
+fn indexed(index: u8) -> [u8; 3] {
+    const SYSTEM: [[u8; 3]; 16] = [
+        [0x00, 0x00, 0x00],
+        [0xcd, 0x31, 0x31],
+        [0x0d, 0xbc, 0x79],
+        [0xe5, 0xe5, 0x10],
+        [0x24, 0x72, 0xc8],
+        [0xbc, 0x3f, 0xbc],
+        [0x11, 0xa8, 0xcd],
+        [0xe5, 0xe5, 0xe5],
+        [0x66, 0x66, 0x66],
+        [0xf1, 0x4c, 0x4c],
+        [0x23, 0xd1, 0x8b],
+        [0xf5, 0xf5, 0x43],
+        [0x3b, 0x8e, 0xea],
+        [0xd6, 0x70, 0xd6],
+        [0x29, 0xb8, 0xdb],
+        [0xff, 0xff, 0xff],
+    ];
+    match index {
+        0..=15 => SYSTEM[index as usize],
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            [level(index / 36), level((index / 6) % 6), level(index % 6)]
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            [gray, gray, gray]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 3] {
+        let index = ((y * image.width + x) * 3) as usize;
+        [
+            image.pixels[index],
+            image.pixels[index + 1],
+            image.pixels[index + 2],
+        ]
+    }
+
+    #[test]
+    fn should_draw_cells_with_their_colours() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(0, 0, "█A", Style::default().fg(Color::Rgb(255, 0, 0)));
+        buffer.set_string(2, 0, " ", Style::default().bg(Color::Blue));
+
+        let image = rasterize(&buffer);
+        assert_eq!((image.width, image.height), (24, 16));
+        // the full block covers the whole cell
+        assert_eq!(pixel(&image, 0, 0), [255, 0, 0]);
+        assert_eq!(pixel(&image, 7, 15), [255, 0, 0]);
+        // the letter leaves part of its cell with the background
+        assert_eq!(pixel(&image, 8, 0), DEFAULT_BACKGROUND);
+        let letter = (8..16).flat_map(|x| (0..16).map(move |y| (x, y)));
+        assert!(letter
+            .map(|(x, y)| pixel(&image, x, y))
+            .any(|p| p == [255, 0, 0]));
+        assert_eq!(pixel(&image, 20, 8), indexed(4));
+    }
+
+    #[test]
+    fn should_convert_indexed_colours() {
+        assert_eq!(rgb(Color::Indexed(16), [1, 1, 1]), [0, 0, 0]);
+        assert_eq!(rgb(Color::Indexed(231), [1, 1, 1]), [255, 255, 255]);
+        assert_eq!(rgb(Color::Indexed(232), [1, 1, 1]), [8, 8, 8]);
+        assert_eq!(rgb(Color::Reset, [1, 1, 1]), [1, 1, 1]);
+    }
+
+    #[test]
+    fn should_scale_and_center_images() {
+        let mut image = Image::new(2, 1);
+        image.fill(1, 0, 1, 1, [255, 0, 0]);
+
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width, scaled.height), (4, 2));
+        assert_eq!(pixel(&scaled, 1, 1), [0, 0, 0]);
+        assert_eq!(pixel(&scaled, 2, 1), [255, 0, 0]);
+
+        let centered = scaled.centered(6, 3, [1, 1, 1]);
+        assert_eq!((centered.width, centered.height), (6, 3));
+        assert_eq!(pixel(&centered, 0, 0), [1, 1, 1]);
+        assert_eq!(pixel(&centered, 1, 0), [0, 0, 0]);
+        assert_eq!(pixel(&centered, 4, 1), [255, 0, 0]);
+        assert_eq!(pixel(&centered, 5, 2), [1, 1, 1]);
+        assert_eq!(
+            scaled.centered(3, 1, [1, 1, 1]).pixels,
+            [0, 0, 0, 0, 0, 0, 255, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::export::frames::FINAL_FRAME_HOLD;
+use crate::export::timeline::{racer_colors, RaceFrame};
+use crate::ui::colors::Colors;
+use crate::utils::date;
+use std::fmt::Write;
+use std::time::Duration;
+
+const WIDTH: u32 = 800;
+const HEADER: u32 = 70;
+const ROW: u32 = 36;
+const BAR_HEIGHT: u32 = 26;
+const NAME_WIDTH: u32 = 160;
+const VALUE_WIDTH: u32 = 70;
+const BACKGROUND: &str = "#1e1e1e";
+const FOREGROUND: &str = "#d4d4d4";
+/// Self-contained SVG that replays the race with SMIL animations, every element
+/// shares the same loop so they stay in sync
+pub fn svg(frames: &[RaceFrame], users: usize, duration: Duration, colors: &mut Colors) -> String {
+    let height = HEADER + users as u32 * ROW + 10;
+    let total = (duration + FINAL_FRAME_HOLD).as_secs_f64();
+    let frame_duration = duration.as_secs_f64() / frames.len().max(1) as f64;
+    let key_times: Vec<String> = (0..frames.len())
+        .map(|i| format!("{:.4}", i as f64 * frame_duration / total))
+        .collect();
+    let timing = format!(r#"dur="{:.3}s" repeatCount="indefinite""#, total);
+    // linear animations need a last key at 1, holding the final values
+    let linear = |values: Vec<String>| {
+        let last = values.last().cloned().unwrap_or_default();
+        format!(
+            r#"values="{};{}" keyTimes="{};1" {}"#,
+            values.join(";"),
+            last,
+            key_times.join(";"),
+            timing
+        )
+    };
+    let visible = |frame: usize| visible_during(&key_times, frame, &timing);
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="14">
+<rect width="100%" height="100%" fill="{BACKGROUND}"/>
+<text x="16" y="28" fill="{FOREGROUND}" font-size="18">Github commit race</text>
+"#,
+        width = WIDTH,
+    );
+
+    for (i, frame) in frames.iter().enumerate() {
+        let month = date::convert_timestamp_to_month_and_year(&frame.week);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="28" fill="{}" font-size="18" text-anchor="end" visibility="hidden">{}{}</text>"#,
+            WIDTH - 16,
+            FOREGROUND,
+            escape(&month),
+            visible(i)
+        );
+    }
+
+    let hidden_y = height + ROW;
+    let max_bar = (WIDTH - NAME_WIDTH - VALUE_WIDTH) as f64;
+    for (racer, color) in racer_colors(frames, colors) {
+        let mut ys = Vec::new();
+        let mut widths = Vec::new();
+        let mut labels = String::new();
+        for (i, frame) in frames.iter().enumerate() {
+            let leader = frame.standings.first().map_or(0, |(_, c)| *c).max(1);
+            let Some(rank) = frame.standings.iter().position(|(a, _)| *a == racer) else {
+                ys.push(format!("0 {}", hidden_y));
+                widths.push("0".to_string());
+                continue;
+            };
+            let commits = frame.standings[rank].1;
+            let width = commits as f64 / leader as f64 * max_bar;
+            ys.push(format!("0 {}", HEADER + rank as u32 * ROW));
+            widths.push(format!("{:.1}", width));
+            let _ = writeln!(
+                labels,
+                r#"<text x="{:.1}" y="18" fill="{}" visibility="hidden">{}{}</text>"#,
+                NAME_WIDTH as f64 + width + 6.0,
+                FOREGROUND,
+                commits,
+                visible(i)
+            );
+        }
+        let _ = write!(
+            svg,
+            r#"<g transform="translate(0 {hidden_y})">
+<animateTransform attributeName="transform" type="translate" {translate}/>
+<text x="{name_x}" y="18" fill="{color}" text-anchor="end">{name}</text>
+<rect x="{NAME_WIDTH}" y="0" width="0" height="{BAR_HEIGHT}" rx="3" fill="{color}"><animate attributeName="width" {width}/></rect>
+{labels}</g>
+"#,
+            translate = linear(ys),
+            name_x = NAME_WIDTH - 8,
+            name = escape(&racer),
+            width = linear(widths),
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Discrete animation that only shows the element during the frame
+fn visible_during(key_times: &[String], frame: usize, timing: &str) -> String {
+    let (values, keys) = match (frame, key_times.get(frame + 1)) {
+        (0, None) => return String::new(),
+        (0, Some(end)) => ("visible;hidden".to_string(), format!("0;{}", end)),
+        (_, Some(end)) => (
+            "hidden;visible;hidden".to_string(),
+            format!("0;{};{}", key_times[frame], end),
+        ),
+        (_, None) => (
+            "hidden;visible".to_string(),
+            format!("0;{}", key_times[frame]),
+        ),
+    };
+    format!(
+        r#"<animate attributeName="visibility" values="{}" keyTimes="{}" calcMode="discrete" {}/>"#,
+        values, keys, timing
+    )
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+    use std::collections::HashMap;
+
+    fn frame(week: u32, standings: &[(&str, u32)]) -> RaceFrame {
+        RaceFrame {
+            week,
+            standings: standings.iter().map(|(a, c)| (a.to_string(), *c)).collect(),
+        }
+    }
+
+    #[test]
+    fn should_animate_every_racer() {
+        let frames = vec![
+            frame(1361059200, &[("octocat", 2), ("hubot", 1)]),
+            frame(1361664000, &[("hubot", 4), ("a<b", 3)]),
+        ];
+        let mut colors = Colors::new(HashMap::from([(
+            "octocat".to_string(),
+            Color::Rgb(255, 136, 0),
+        )]));
+        let svg = svg(&frames, 2, Duration::from_secs(2), &mut colors);
+
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"152\""));
+        assert!(svg.ends_with("</svg>\n"));
+        // the loop lasts the race plus the final hold, the second frame starts after 1s
+        assert!(svg.contains(r#"keyTimes="0.0000;0.2000;1" dur="5.000s""#));
+        assert!(svg.contains(r#"values="0 106;0 70;0 70""#));
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert!(svg
+            .contains(r##"<text x="152" y="18" fill="#ff8800" text-anchor="end">octocat</text>"##));
+        assert_eq!(svg.matches("<g ").count(), 3);
+        assert!(svg.contains(
+            r#"Feb 2013<animate attributeName="visibility" values="visible;hidden" keyTimes="0;0.2000""#
+        ));
+    }
+}
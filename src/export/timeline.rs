@@ -0,0 +1,95 @@
+use crate::core::commits::{rank_authors, Commits};
+use crate::export::raster::{rgb, DEFAULT_FOREGROUND};
+use crate::ui::colors::Colors;
+use ratatui::style::Color;
+
+/// Standings of the race at a week, only the racers on screen
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceFrame {
+    pub week: u32,
+    pub standings: Vec<(String, u32)>,
+}
+
+/// Weeks of the race with the top `users` authors of each one. Long histories are
+/// sampled down to `max_frames`, keeping the last week so the final standings are exact
+pub fn race_timeline(commits: &Commits, users: usize, max_frames: usize) -> Vec<RaceFrame> {
+    let weeks = commits.sorted_weeks();
+    let step = weeks.len().div_ceil(max_frames.max(1)).max(1);
+    let mut sampled: Vec<u32> = weeks.iter().step_by(step).copied().collect();
+    if let (Some(last), Some(last_sampled)) = (weeks.last(), sampled.last()) {
+        if last != last_sampled {
+            sampled.push(*last);
+        }
+    }
+    sampled
+        .into_iter()
+        .filter_map(|week| {
+            let mut standings = rank_authors(commits.sum_commits.get(&week)?);
+            standings.truncate(users);
+            Some(RaceFrame { week, standings })
+        })
+        .collect()
+}
+
+/// Every author that reaches the screen at some point, in order of appearance
+pub fn racers(frames: &[RaceFrame]) -> Vec<String> {
+    let mut racers: Vec<String> = Vec::new();
+    for frame in frames {
+        for (author, _) in frame.standings.iter() {
+            if !racers.contains(author) {
+                racers.push(author.clone());
+            }
+        }
+    }
+    racers
+}
+
+/// Racers in order of appearance with the `#rrggbb` of their colour, given frame by
+/// frame like the terminal does
+pub fn racer_colors(frames: &[RaceFrame], colors: &mut Colors) -> Vec<(String, String)> {
+    for frame in frames {
+        let visible: Vec<&str> = frame.standings.iter().map(|(a, _)| a.as_str()).collect();
+        colors.allocate(&visible);
+    }
+    racers(frames)
+        .into_iter()
+        .map(|racer| {
+            let color = hex_color(colors.color(&racer));
+            (racer, color)
+        })
+        .collect()
+}
+
+pub fn hex_color(color: Color) -> String {
+    let [r, g, b] = rgb(color, DEFAULT_FOREGROUND);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::commits::get_commits_per_week;
+    use crate::github::contributors::contributor;
+
+    #[test]
+    fn should_sample_weeks_keeping_the_last_one() {
+        let contributors = vec![
+            contributor("octocat", &[1, 1, 1, 1, 1]),
+            contributor("hubot", &[0, 0, 0, 0, 9]),
+            contributor("monalisa", &[0, 2, 0, 0, 0]),
+        ];
+        let commits = get_commits_per_week(contributors, vec![]).unwrap();
+        let frames = race_timeline(&commits, 2, 2);
+
+        let weeks: Vec<u32> = frames.iter().map(|f| f.week).collect();
+        assert_eq!(
+            weeks,
+            vec![1361059200, 1361059200 + 3 * 604800, 1361059200 + 4 * 604800]
+        );
+        assert_eq!(
+            frames[2].standings,
+            vec![("hubot".to_string(), 9), ("octocat".to_string(), 5)]
+        );
+        assert_eq!(racers(&frames), vec!["octocat", "hubot", "monalisa"]);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::app::App;
+use crate::error::Error;
+use crate::export::frames::{race_frames, FINAL_FRAME_HOLD};
+use crate::export::raster::{rasterize, Image, CELL_HEIGHT, CELL_WIDTH, DEFAULT_BACKGROUND};
+use ratatui::buffer::Buffer;
+use std::time::Duration;
+
+/// Size of the video frames in pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl std::str::FromStr for Resolution {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let size = value
+            .split_once(['x', 'X'])
+            .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)));
+        match size {
+            Some((width, height)) if width > 0 && height > 0 => Ok(Resolution { width, height }),
+            _ => Err(format!(
+                "'{}' is not a resolution, use WIDTHxHEIGHT ie: '1920x1080'",
+                value
+            )),
+        }
+    }
+}
+
+/// Columns and rows of the screen that fills the resolution, and the zoom of its
+/// cells. The zoom is a whole number so the glyphs stay sharp, it is the biggest one
+/// that still shows `width` x `height` cells
+pub fn screen_size(resolution: Resolution, width: u16, height: u16) -> (u16, u16, u32) {
+    let scale = (resolution.width / (CELL_WIDTH * width.max(1) as u32))
+        .min(resolution.height / (CELL_HEIGHT * height.max(1) as u32))
+        .max(1);
+    let cells = |pixels: u32, cell: u32| (pixels / (cell * scale)).clamp(1, u16::MAX as u32) as u16;
+    (
+        cells(resolution.width, CELL_WIDTH),
+        cells(resolution.height, CELL_HEIGHT),
+        scale,
+    )
+}
+
+#[derive(Clone, Copy)]
+pub struct VideoOptions {
+    /// Size of the frames, the screen size in cells when it is not set
+    pub resolution: Option<Resolution>,
+    pub tick_rate: Duration,
+    pub total_ticks: u32,
+    pub fps: u32,
+}
+
+/// Draws the race at a constant `fps`, calling `on_frame` with the number of every
+/// frame. The final standings are held a few seconds at the end, an error of `on_frame`
+/// stops the frames
+pub fn video_frames<E>(
+    app: &mut App,
+    width: u16,
+    height: u16,
+    options: &VideoOptions,
+    mut on_frame: impl FnMut(usize, &Image) -> Result<(), E>,
+) -> Result<(), E> {
+    let VideoOptions {
+        resolution,
+        tick_rate,
+        total_ticks,
+        fps,
+    } = *options;
+    let (width, height, scale) = match resolution {
+        Some(resolution) => screen_size(resolution, width, height),
+        None => (width, height, 1),
+    };
+    let mut screens: Vec<(Duration, Buffer)> = Vec::new();
+    race_frames(app, width, height, total_ticks, |tick, buffer| {
+        if screens.last().is_none_or(|(_, last)| last != buffer) {
+            screens.push((tick_rate * tick, buffer.clone()));
+        }
+    });
+    let Some((end, _)) = screens.last() else {
+        return Ok(());
+    };
+
+    let fps = fps.max(1);
+    let count = ((*end + FINAL_FRAME_HOLD).as_secs_f64() * fps as f64).ceil() as usize;
+    let mut image: Option<(usize, Image)> = None;
+    for number in 0..count {
+        let time = Duration::from_secs(number as u64) / fps;
+        // the screen shown at that time is the last one drawn before it
+        let index = screens
+            .partition_point(|(start, _)| *start <= time)
+            .saturating_sub(1);
+        if image.as_ref().is_none_or(|(last, _)| *last != index) {
+            let mut frame = rasterize(&screens[index].1).scaled(scale);
+            if let Some(resolution) = resolution {
+                frame = frame.centered(resolution.width, resolution.height, DEFAULT_BACKGROUND);
+            }
+            image = Some((index, frame));
+        }
+        if let Some((_, frame)) = image.as_ref() {
+            on_frame(number, frame)?;
+        }
+    }
+    Ok(())
+}
+
+/// Binary PPM, the simplest image format ffmpeg reads
+pub fn ppm(image: &Image) -> Vec<u8> {
+    let mut output = format!("P6\n{} {}\n255\n", image.width, image.height).into_bytes();
+    output.extend_from_slice(&image.pixels);
+    output
+}
+
+pub fn png(image: &Image) -> Result<Vec<u8>, Error> {
+    let encode_error = |e: png::EncodingError| Error::Encode {
+        format: "PNG".to_string(),
+        message: e.to_string(),
+    };
+    let mut output = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut output, image.width, image.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(encode_error)?;
+        writer
+            .write_image_data(&image.pixels)
+            .map_err(encode_error)?;
+    }
+    Ok(output)
+}
+
+/// Header of a YUV4MPEG2 stream, the frames keep every chroma sample (4:4:4)
+pub fn y4m_header(image: &Image, fps: u32) -> String {
+    format!(
+        "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444\n",
+        image.width, image.height, fps
+    )
+}
+
+/// Frame of a YUV4MPEG2 stream, the Y, U and V planes in BT.601 limited range
+pub fn y4m_frame(image: &Image) -> Vec<u8> {
+    let size = image.pixels.len() / 3;
+    let mut output = Vec::with_capacity(6 + size * 3);
+    output.extend_from_slice(b"FRAME\n");
+    let planes: [fn(f64, f64, f64) -> f64; 3] = [
+        |r, g, b| 16.0 + (65.481 * r + 128.553 * g + 24.966 * b) / 255.0,
+        |r, g, b| 128.0 + (-37.797 * r - 74.203 * g + 112.0 * b) / 255.0,
+        |r, g, b| 128.0 + (112.0 * r - 93.786 * g - 18.214 * b) / 255.0,
+    ];
+    for plane in planes {
+        output.extend(image.pixels.chunks_exact(3).map(|pixel| {
+            let [r, g, b] = [pixel[0], pixel[1], pixel[2]].map(f64::from);
+            plane(r, g, b).round() as u8
+        }));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{InputMode, Users};
+    use crate::github::contributors::contributor;
+
+    #[test]
+    fn should_parse_resolutions() {
+        assert_eq!(
+            "1920x1080".parse(),
+            Ok(Resolution {
+                width: 1920,
+                height: 1080
+            })
+        );
+        assert!("1920".parse::<Resolution>().is_err());
+        assert!("0x1080".parse::<Resolution>().is_err());
+        // 100x30 cells are 800x480 pixels, they fit twice in 1920x1080
+        assert_eq!(
+            screen_size("1920x1080".parse().unwrap(), 100, 30),
+            (120, 33, 2)
+        );
+    }
+
+    #[test]
+    fn should_draw_frames_at_a_constant_rate() {
+        let mut app = App::new(vec![], Users::Count(2), false);
+        app.set_contributors(vec![
+            contributor("octocat", &[1, 2, 3, 4]),
+            contributor("hubot", &[3, 0, 0, 0]),
+        ])
+        .unwrap();
+        app.input_mode = InputMode::Normal;
+
+        let options = VideoOptions {
+            resolution: Some(Resolution {
+                width: 250,
+                height: 200,
+            }),
+            tick_rate: Duration::from_millis(100),
+            total_ticks: 4,
+            fps: 5,
+        };
+        let mut frames = Vec::new();
+        video_frames(&mut app, 30, 12, &options, |number, image| {
+            frames.push((number, image.width, image.height));
+            Ok::<_, Error>(())
+        })
+        .unwrap();
+        // the screen stops changing after 200ms, then the final frame is held 3s
+        assert_eq!(frames.len(), 16);
+        assert_eq!(frames[15], (15, 250, 200));
+    }
+
+    #[test]
+    fn should_encode_frames() {
+        let image = Image {
+            width: 1,
+            height: 1,
+            pixels: vec![255, 255, 255],
+        };
+        assert_eq!(ppm(&image), b"P6\n1 1\n255\n\xff\xff\xff");
+        assert!(png(&image).unwrap().starts_with(b"\x89PNG"));
+        assert_eq!(
+            y4m_header(&image, 10),
+            "YUV4MPEG2 W1 H1 F10:1 Ip A1:1 C444\n"
+        );
+        assert_eq!(y4m_frame(&image), b"FRAME\n\xeb\x80\x80");
+    }
+}
@@ -1,17 +1,273 @@
-pub fn get_contributors(url: &str) -> Result<String, std::io::Error> {
-    match ureq::get(url)
-        .set("Content-Type", "application/json")
-        .set("Accept", "application/json")
-        .call()
-    {
-        Ok(response) => Ok(response.into_string()?),
-        Err(e) => Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            format!(
-                "Could not load contributors from api {} with error: {}",
-                url,
-                e.to_string()
+use crate::error::Error;
+use std::thread;
+use std::time::Duration;
+
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub struct ApiOptions {
+    pub retries: u32,
+    pub backoff: Duration,
+}
+
+impl Default for ApiOptions {
+    fn default() -> Self {
+        ApiOptions {
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Quota left as reported by the `X-RateLimit-*` headers
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RateLimit {
+    pub remaining: Option<u32>,
+    pub reset: Option<u32>,
+}
+
+pub struct ApiResponse {
+    pub body: String,
+    pub rate_limit: RateLimit,
+}
+
+/// Endpoint of the insights page with the weekly commits of every contributor
+pub fn contributors_url(repository: &str) -> String {
+    format!("https://github.com/{}/graphs/contributors-data", repository)
+}
+
+/// Downloads the contributors, retrying with exponential backoff while Github is
+/// computing the statistics (202), rate limiting (429) or failing (5xx)
+pub fn get_contributors(
+    agent: &ureq::Agent,
+    url: &str,
+    options: &ApiOptions,
+) -> Result<ApiResponse, Error> {
+    get_contributors_notifying(agent, url, options, |_| {})
+}
+
+/// Same as `get_contributors`, calling `on_retry` with the delay before every retry
+pub fn get_contributors_notifying(
+    agent: &ureq::Agent,
+    url: &str,
+    options: &ApiOptions,
+    mut on_retry: impl FnMut(Duration),
+) -> Result<ApiResponse, Error> {
+    let mut attempt = 0;
+    loop {
+        let result = agent
+            .get(url)
+            .set("Content-Type", "application/json")
+            .set("Accept", "application/json")
+            .call();
+        let (status, retry_after, error) = match result {
+            Ok(response) if response.status() != 202 => {
+                let rate_limit = rate_limit(&response);
+                let body = response.into_string().map_err(|e| Error::Network {
+                    url: url.to_string(),
+                    message: e.to_string(),
+                })?;
+                return Ok(ApiResponse { body, rate_limit });
+            }
+            Ok(response) => (202, retry_after(&response), None),
+            Err(ureq::Error::Status(status, response)) => {
+                let error = if status == 429 || rate_limit(&response).remaining == Some(0) {
+                    Error::RateLimited {
+                        url: url.to_string(),
+                        reset: rate_limit(&response).reset,
+                    }
+                } else {
+                    Error::HttpStatus {
+                        url: url.to_string(),
+                        status,
+                    }
+                };
+                (status, retry_after(&response), Some(error))
+            }
+            Err(e) => {
+                return Err(Error::Network {
+                    url: url.to_string(),
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        let retryable = status == 202 || status == 429 || status >= 500;
+        if !retryable || attempt >= options.retries {
+            return Err(error.unwrap_or(Error::HttpStatus {
+                url: url.to_string(),
+                status,
+            }));
+        }
+        let delay = backoff_delay(attempt, options.backoff, retry_after);
+        on_retry(delay);
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+/// `Retry-After` wins when present, otherwise the wait doubles on every attempt
+fn backoff_delay(attempt: u32, backoff: Duration, retry_after: Option<Duration>) -> Duration {
+    match retry_after {
+        Some(retry_after) => retry_after.min(MAX_RETRY_AFTER),
+        None => backoff.saturating_mul(2u32.saturating_pow(attempt)),
+    }
+}
+
+fn retry_after(response: &ureq::Response) -> Option<Duration> {
+    let seconds = response.header("Retry-After")?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+fn rate_limit(response: &ureq::Response) -> RateLimit {
+    let header = |name: &str| response.header(name)?.trim().parse().ok();
+    RateLimit {
+        remaining: header("X-RateLimit-Remaining"),
+        reset: header("X-RateLimit-Reset"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: {}\r\n{}\r\n{}",
+            status,
+            body.len(),
+            headers,
+            body
+        )
+    }
+
+    /// Answers every request with the next response and counts the requests
+    fn serve(responses: Vec<String>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/graphs/contributors-data",
+            listener.local_addr().unwrap()
+        );
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buffer = [0; 4096];
+                let _ = stream.read(&mut buffer);
+                counter.fetch_add(1, Ordering::SeqCst);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn agent() -> ureq::Agent {
+        ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(5))
+            .build()
+    }
+
+    fn options(retries: u32) -> ApiOptions {
+        ApiOptions {
+            retries,
+            backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn should_retry_while_statistics_are_computed() {
+        let (url, requests) = serve(vec![
+            response("202 Accepted", "", ""),
+            response("503 Service Unavailable", "", ""),
+            response(
+                "200 OK",
+                "X-RateLimit-Remaining: 42\r\nX-RateLimit-Reset: 1700000000\r\n",
+                "[]",
             ),
-        )),
+        ]);
+
+        let mut delays = Vec::new();
+        let response = get_contributors_notifying(&agent(), url.as_str(), &options(3), |delay| {
+            delays.push(delay)
+        })
+        .unwrap();
+        assert_eq!(response.body, "[]");
+        assert_eq!(delays, [Duration::from_millis(1), Duration::from_millis(2)]);
+        assert_eq!(
+            response.rate_limit,
+            RateLimit {
+                remaining: Some(42),
+                reset: Some(1700000000),
+            }
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn should_give_up_after_retries() {
+        let (url, requests) = serve(vec![
+            response("500 Internal Server Error", "", ""),
+            response("500 Internal Server Error", "", ""),
+        ]);
+
+        let error = get_contributors(&agent(), url.as_str(), &options(1))
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::HttpStatus { status: 500, .. }));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn should_not_retry_not_found() {
+        let (url, requests) = serve(vec![response("404 Not Found", "", "")]);
+
+        let error = get_contributors(&agent(), url.as_str(), &options(3))
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::HttpStatus { status: 404, .. }));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn should_report_rate_limit_with_reset() {
+        let (url, _) = serve(vec![
+            response("429 Too Many Requests", "Retry-After: 0\r\n", ""),
+            response(
+                "429 Too Many Requests",
+                "Retry-After: 0\r\nX-RateLimit-Remaining: 0\r\nX-RateLimit-Reset: 1700000000\r\n",
+                "",
+            ),
+        ]);
+
+        let error = get_contributors(&agent(), url.as_str(), &options(1))
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            Error::RateLimited {
+                reset: Some(1700000000),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn should_compute_backoff_delay() {
+        let backoff = Duration::from_secs(1);
+        assert_eq!(backoff_delay(0, backoff, None), Duration::from_secs(1));
+        assert_eq!(backoff_delay(3, backoff, None), Duration::from_secs(8));
+        assert_eq!(
+            backoff_delay(3, backoff, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            backoff_delay(0, backoff, Some(Duration::from_secs(3600))),
+            MAX_RETRY_AFTER
+        );
     }
 }
@@ -0,0 +1,175 @@
+use crate::error::Error;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::CertificateDer;
+use std::sync::Arc;
+use std::time::Duration;
+
+const GITHUB_HOST: &str = "github.com";
+
+#[derive(Clone, Debug)]
+pub struct ClientOptions {
+    pub timeout: Duration,
+    pub proxy: Option<String>,
+    pub ca_file: Option<String>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            timeout: Duration::from_secs(10),
+            proxy: None,
+            ca_file: None,
+        }
+    }
+}
+
+/// Builds the agent shared by every request to Github, with the proxy and the extra
+/// root certificates applied once
+pub fn new_agent(options: &ClientOptions) -> Result<ureq::Agent, Error> {
+    let mut builder = ureq::AgentBuilder::new()
+        .timeout(options.timeout)
+        .try_proxy_from_env(false);
+    let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    if let Some(proxy) = select_proxy(options.proxy.as_deref(), GITHUB_HOST, env) {
+        // the proxy url is not printed, it may contain the credentials
+        let proxy = ureq::Proxy::new(proxy)
+            .map_err(|e| Error::Config(format!("invalid proxy url: {}", e)))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(ca_file) = options.ca_file.as_ref() {
+        builder = builder.tls_config(tls_config(ca_file)?);
+    }
+    Ok(builder.build())
+}
+
+/// The explicit proxy wins, otherwise `HTTPS_PROXY` or `ALL_PROXY` are used unless
+/// `NO_PROXY` matches the host
+fn select_proxy(
+    explicit: Option<&str>,
+    host: &str,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    if let Some(proxy) = explicit {
+        return Some(proxy.to_string());
+    }
+    let no_proxy = env("NO_PROXY")
+        .or_else(|| env("no_proxy"))
+        .unwrap_or_default();
+    let excluded = no_proxy.split(',').map(str::trim).any(|entry| {
+        let entry = entry.trim_start_matches('.').to_lowercase();
+        entry == "*"
+            || (!entry.is_empty() && (host == entry || host.ends_with(&format!(".{}", entry))))
+    });
+    if excluded {
+        return None;
+    }
+    ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .find_map(|name| env(name))
+}
+
+/// Default web roots plus the certificates of the PEM file, ie: a corporate proxy CA
+fn tls_config(ca_file: &str) -> Result<Arc<rustls::ClientConfig>, Error> {
+    let mut roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let invalid = |e: String| Error::Config(format!("invalid certificates in {}: {}", ca_file, e));
+    let mut added = 0;
+    for certificate in CertificateDer::pem_file_iter(ca_file).map_err(|e| invalid(e.to_string()))? {
+        let certificate = certificate.map_err(|e| invalid(e.to_string()))?;
+        roots.add(certificate).map_err(|e| invalid(e.to_string()))?;
+        added += 1;
+    }
+    if added == 0 {
+        return Err(invalid("no certificate found".to_string()));
+    }
+
+    let config = rustls::ClientConfig::builder_with_provider(
+        rustls::crypto::ring::default_provider().into(),
+    )
+    .with_safe_default_protocol_versions()
+    .map_err(|e| invalid(e.to_string()))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+
+    const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBgTCCASegAwIBAgIUK4B0T6sqSa99U590JjhjzKSRQyswCgYIKoZIzj0EAwIw
+FTETMBEGA1UEAwwKcHJveHkudGVzdDAgFw0yNjEwMTkwNjM3MTlaGA8yMTI2MDky
+NTA2MzcxOVowFTETMBEGA1UEAwwKcHJveHkudGVzdDBZMBMGByqGSM49AgEGCCqG
+SM49AwEHA0IABDZHgmDrzSB9YbCOnKJSmmtrjvvZw5tgRyKQaZ6yDC3RO0nS73V+
+eJgb6wV/dXu6sqohQfNbypHk7KEbDAvCB7qjUzBRMB0GA1UdDgQWBBSAGH5Y2FAr
+pThKRJzqDab18aAHGDAfBgNVHSMEGDAWgBSAGH5Y2FArpThKRJzqDab18aAHGDAP
+BgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0gAMEUCIQC8d8wnlmjN7R5tUrj6
+dxQU1vVwmD0Gqr7pfFoNe/HjVAIgFqyRvPLKpO5QZdvq4G9Ftg8mIXAnVyWZH6C6
+s7OkCgw=
+-----END CERTIFICATE-----
+";
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn should_select_proxy() {
+        let proxy = "http://proxy:3128";
+        let https = env(&[("HTTPS_PROXY", proxy)]);
+        assert_eq!(select_proxy(None, "github.com", &https), Some(proxy.into()));
+        assert_eq!(
+            select_proxy(Some("http://other:8080"), "github.com", &https),
+            Some("http://other:8080".into())
+        );
+        assert_eq!(select_proxy(None, "github.com", env(&[])), None);
+        assert_eq!(
+            select_proxy(None, "github.com", env(&[("all_proxy", proxy)])),
+            Some(proxy.into())
+        );
+    }
+
+    #[test]
+    fn should_skip_proxy_when_host_is_excluded() {
+        let proxy = "http://proxy:3128";
+        for no_proxy in ["*", "github.com", "localhost, .github.com", "GITHUB.COM"] {
+            let vars = env(&[("HTTPS_PROXY", proxy), ("NO_PROXY", no_proxy)]);
+            assert_eq!(select_proxy(None, "github.com", vars), None, "{}", no_proxy);
+        }
+        let vars = env(&[("HTTPS_PROXY", proxy), ("no_proxy", "hub.com,localhost")]);
+        assert_eq!(select_proxy(None, "github.com", vars), Some(proxy.into()));
+    }
+
+    #[test]
+    fn should_load_extra_root_certificates() {
+        // unique per process, concurrent runs of the tests must not share the file
+        let path = std::env::temp_dir().join(format!(
+            "gh-commit-race-{}-should_load_extra_root_certificates.pem",
+            std::process::id()
+        ));
+        fs::write(&path, CERTIFICATE).unwrap();
+        assert!(tls_config(path.to_str().unwrap()).is_ok());
+
+        fs::write(&path, "not a certificate").unwrap();
+        let error = tls_config(path.to_str().unwrap()).err().unwrap();
+        assert!(matches!(error, Error::Config(_)));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_reject_invalid_proxy() {
+        let options = ClientOptions {
+            proxy: Some("ftp://proxy".to_string()),
+            ..ClientOptions::default()
+        };
+        assert!(matches!(new_agent(&options), Err(Error::Config(_))));
+    }
+}
@@ -1,5 +1,6 @@
+use crate::error::Error;
 use serde::{Deserialize, Serialize};
-use serde_json::Error;
+use std::collections::HashMap;
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Contributor {
@@ -22,12 +23,67 @@ pub struct Week {
 }
 
 pub fn serialize_contributors(json_content: &str) -> Result<Vec<Contributor>, Error> {
-    serde_json::from_str(json_content)
+    Ok(serde_json::from_str(json_content)?)
+}
+
+/// Renames the logins that have an alias, contributors ending with the same login are merged
+pub fn apply_aliases(
+    contributors: Vec<Contributor>,
+    aliases: &HashMap<String, String>,
+) -> Vec<Contributor> {
+    let mut merged: Vec<Contributor> = Vec::new();
+    for mut contributor in contributors {
+        if let Some(alias) = aliases.get(&contributor.author.login) {
+            contributor.author.login = alias.clone();
+        }
+        let Some(existing) = merged
+            .iter_mut()
+            .find(|c| c.author.login == contributor.author.login)
+        else {
+            merged.push(contributor);
+            continue;
+        };
+        existing.total += contributor.total;
+        for week in contributor.weeks {
+            match existing.weeks.iter_mut().find(|w| w.w == week.w) {
+                Some(existing_week) => {
+                    existing_week.a += week.a;
+                    existing_week.d += week.d;
+                    existing_week.c += week.c;
+                }
+                None => existing.weeks.push(week),
+            }
+        }
+    }
+    merged
+}
+
+/// Contributor of the tests with a week per entry of `commits` from Feb 17 2013, it adds
+/// ten lines and deletes one per commit
+#[cfg(test)]
+pub(crate) fn contributor(login: &str, commits: &[u32]) -> Contributor {
+    Contributor {
+        total: commits.iter().sum(),
+        author: Author {
+            login: login.to_string(),
+        },
+        weeks: commits
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Week {
+                w: 1361059200 + i as u32 * 604800,
+                a: c * 10,
+                d: *c,
+                c: *c,
+            })
+            .collect(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::github::contributors::serialize_contributors;
+    use crate::github::contributors::{apply_aliases, serialize_contributors};
+    use std::collections::HashMap;
 
     #[test]
     fn should_parse_string_to_contributors() {
@@ -59,4 +115,34 @@ mod tests {
         assert_eq!(contributors[0].weeks[0].d, 0);
         assert_eq!(contributors[0].weeks[0].c, 1);
     }
+
+    #[test]
+    fn should_return_parse_error_on_invalid_json() {
+        let error = serialize_contributors("<html>not json</html>")
+            .err()
+            .unwrap();
+        assert!(matches!(error, crate::error::Error::Parse(_)));
+    }
+
+    #[test]
+    fn should_merge_contributors_with_aliases() {
+        let json_content = r#"
+        [
+            {"total": 1, "author": {"login": "octocat"}, "weeks": [{"w": 1, "a": 1, "d": 0, "c": 1}]},
+            {"total": 3, "author": {"login": "octocat-work"}, "weeks": [{"w": 1, "a": 2, "d": 1, "c": 2}, {"w": 2, "a": 0, "d": 0, "c": 1}]},
+            {"total": 2, "author": {"login": "octobot"}, "weeks": [{"w": 1, "a": 0, "d": 0, "c": 2}]}
+        ]
+        "#;
+        let contributors = serialize_contributors(json_content).unwrap();
+        let aliases = HashMap::from([("octocat-work".to_string(), "octocat".to_string())]);
+
+        let contributors = apply_aliases(contributors, &aliases);
+        assert_eq!(contributors.len(), 2);
+        assert_eq!(contributors[0].author.login, "octocat");
+        assert_eq!(contributors[0].total, 4);
+        assert_eq!(contributors[0].weeks.len(), 2);
+        assert_eq!(contributors[0].weeks[0].c, 3);
+        assert_eq!(contributors[0].weeks[0].a, 3);
+        assert_eq!(contributors[1].author.login, "octobot");
+    }
 }
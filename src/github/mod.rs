@@ -1,2 +1,4 @@
 pub mod api;
+pub mod client;
 pub mod contributors;
+pub mod repository;
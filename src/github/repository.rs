@@ -0,0 +1,92 @@
+/// Normalises the ways of writing a Github repository to 'owner/repo', ie:
+/// 'https://github.com/owner/repo', 'github.com/owner/repo/tree/main' or
+/// 'git@github.com:owner/repo.git'
+pub fn parse_repository(value: &str) -> Result<String, String> {
+    let invalid = || format!("'{}' is not a Github repository, ie: 'owner/repo'", value);
+    let trimmed = value.trim();
+    let path = trimmed
+        .strip_prefix("git@github.com:")
+        .or_else(|| strip_host(trimmed))
+        .unwrap_or(trimmed);
+
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let (Some(owner), Some(repo)) = (segments.next(), segments.next()) else {
+        return Err(invalid());
+    };
+    // extra segments are only allowed after a host, ie: '/tree/main' or '/pulls'
+    if path == trimmed && segments.next().is_some() {
+        return Err(invalid());
+    }
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    if !is_valid_name(owner) || !is_valid_name(repo) {
+        return Err(invalid());
+    }
+    Ok(format!("{}/{}", owner, repo))
+}
+
+/// Path after the host of urls like 'https://github.com/...' or 'ssh://git@github.com/...'
+fn strip_host(value: &str) -> Option<&str> {
+    let without_scheme = ["https://", "http://", "ssh://"]
+        .iter()
+        .find_map(|scheme| value.strip_prefix(scheme))
+        .unwrap_or(value);
+    let without_user = without_scheme
+        .strip_prefix("git@")
+        .unwrap_or(without_scheme);
+    let without_www = without_user.strip_prefix("www.").unwrap_or(without_user);
+    let path = without_www.strip_prefix("github.com")?;
+    path.strip_prefix('/')
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_normalise_repository() {
+        for value in [
+            "rust-lang/rust",
+            " rust-lang/rust/ ",
+            "https://github.com/rust-lang/rust",
+            "http://www.github.com/rust-lang/rust.git",
+            "github.com/rust-lang/rust/tree/master/src",
+            "git@github.com:rust-lang/rust.git",
+            "ssh://git@github.com/rust-lang/rust.git",
+        ] {
+            assert_eq!(
+                parse_repository(value),
+                Ok("rust-lang/rust".to_string()),
+                "{}",
+                value
+            );
+        }
+        assert_eq!(
+            parse_repository("angel-git/gh-commit-race"),
+            Ok("angel-git/gh-commit-race".to_string())
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_repository() {
+        for value in [
+            "",
+            "rust",
+            "rust-lang/rust/extra",
+            "https://gitlab.com/rust-lang/rust",
+            "https://github.com/rust-lang",
+            "owner/re po",
+            "../rust",
+        ] {
+            assert!(parse_repository(value).is_err(), "{}", value);
+        }
+    }
+}
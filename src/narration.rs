@@ -0,0 +1,160 @@
+use crate::utils::number::{ordinal, thousands};
+
+/// Racers named after the leader when the race starts and ends
+const PODIUM: usize = 3;
+
+/// Sentences telling how the standings changed since the previous frame, `previous` is
+/// empty before the first one. `top` is the amount of racers followed
+pub fn events(previous: &[(String, u32)], current: &[(String, u32)], top: usize) -> Vec<String> {
+    let previous = racing(previous, top);
+    let current = racing(current, top);
+    if previous.is_empty() {
+        return podium(current, "leads")
+            .map(|podium| format!("the race starts, {}", podium))
+            .into_iter()
+            .collect();
+    }
+    let rank_before = |author: &str| previous.iter().position(|(a, _)| *a == author);
+
+    let mut events = Vec::new();
+    for (rank, (author, commits)) in current.iter().enumerate() {
+        let place = if rank == 0 {
+            "the lead".to_string()
+        } else {
+            format!("{} place", ordinal(rank + 1))
+        };
+        let commits = plural(*commits);
+        match rank_before(author) {
+            // with a full top, a racer coming in pushes another one out
+            None if previous.len() == top => events.push(format!(
+                "{} enters the top {} at {} place with {}",
+                author,
+                top,
+                ordinal(rank + 1),
+                commits
+            )),
+            None => events.push(format!(
+                "{} enters at {} place with {}",
+                author,
+                ordinal(rank + 1),
+                commits
+            )),
+            Some(before) if before > rank => {
+                let overtaken: Vec<&str> = current[rank + 1..]
+                    .iter()
+                    .map(|(a, _)| a.as_str())
+                    .filter(|a| rank_before(a).is_some_and(|r| r < before))
+                    .collect();
+                events.push(if overtaken.is_empty() {
+                    format!("{} moves up to {} with {}", author, place, commits)
+                } else {
+                    format!(
+                        "{} overtakes {} for {} with {}",
+                        author,
+                        list(&overtaken),
+                        place,
+                        commits
+                    )
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for (author, _) in previous.iter() {
+        if !current.iter().any(|(a, _)| a == author) {
+            events.push(format!("{} drops out of the top {}", author, top));
+        }
+    }
+    events
+}
+
+/// Sentence announcing the winner once the race is over
+pub fn finish(leaders: &[(String, u32)], top: usize) -> Option<String> {
+    let leaders = racing(leaders, top);
+    podium(leaders, "wins").map(|podium| format!("the race is over, {}", podium))
+}
+
+/// Racers with at least a commit, the others did not start yet
+fn racing(leaders: &[(String, u32)], top: usize) -> &[(String, u32)] {
+    let started = leaders.iter().take(top).take_while(|(_, c)| *c > 0).count();
+    &leaders[..started]
+}
+
+/// Leader and the first racers behind, ie: "alice leads with 5 commits, ahead of bob with 3"
+fn podium(leaders: &[(String, u32)], verb: &str) -> Option<String> {
+    let ((leader, commits), others) = leaders.split_first()?;
+    let mut text = format!("{} {} with {}", leader, verb, plural(*commits));
+    let others: Vec<String> = others
+        .iter()
+        .take(PODIUM - 1)
+        .map(|(author, commits)| format!("{} with {}", author, thousands(*commits)))
+        .collect();
+    if !others.is_empty() {
+        let others: Vec<&str> = others.iter().map(String::as_str).collect();
+        text.push_str(&format!(", ahead of {}", list(&others)));
+    }
+    Some(text)
+}
+
+fn plural(commits: u32) -> String {
+    match commits {
+        1 => "1 commit".to_string(),
+        _ => format!("{} commits", thousands(commits)),
+    }
+}
+
+/// Names joined like in a sentence, ie: "alice, bob and carol"
+fn list(names: &[&str]) -> String {
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, others)) => format!("{} and {}", others.join(", "), last),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standings(entries: &[(&str, u32)]) -> Vec<(String, u32)> {
+        entries.iter().map(|(a, c)| (a.to_string(), *c)).collect()
+    }
+
+    #[test]
+    fn should_narrate_rank_changes() {
+        let start = standings(&[("bob", 1100), ("carol", 900), ("alice", 800), ("dave", 0)]);
+        assert_eq!(
+            events(&[], &start, 10),
+            ["the race starts, bob leads with 1,100 commits, ahead of carol with 900 and alice with 800"]
+        );
+
+        let next = standings(&[("bob", 1300), ("alice", 1204), ("carol", 950), ("dave", 1)]);
+        assert_eq!(
+            events(&start, &next, 10),
+            [
+                "alice overtakes carol for 2nd place with 1,204 commits",
+                "dave enters at 4th place with 1 commit"
+            ]
+        );
+
+        let last = standings(&[
+            ("alice", 1500),
+            ("dave", 1400),
+            ("bob", 1350),
+            ("carol", 950),
+        ]);
+        assert_eq!(
+            events(&next, &last, 3),
+            [
+                "alice overtakes bob for the lead with 1,500 commits",
+                "dave enters the top 3 at 2nd place with 1,400 commits",
+                "carol drops out of the top 3"
+            ]
+        );
+        assert_eq!(
+            finish(&last, 3).unwrap(),
+            "the race is over, alice wins with 1,500 commits, ahead of dave with 1,400 and bob with 1,350"
+        );
+        assert_eq!(finish(&standings(&[("alice", 0)]), 3), None);
+    }
+}
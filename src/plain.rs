@@ -0,0 +1,213 @@
+use crate::app::{App, Users};
+use crate::narration;
+use crate::utils::date;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Racers followed by the narration when the amount of users is 'auto'
+const NARRATED_USERS: usize = 10;
+
+/// What is printed for every week of the race
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlainOutput {
+    /// Every contributor with its commits
+    Leaderboard,
+    /// Only the changes of rank
+    Changes,
+    /// Sentences for screen readers, ie: "June 2019: alice overtakes bob for 2nd place"
+    Narration,
+}
+
+/// Streams the race as text lines instead of drawing it, for pipes and CI logs where
+/// there is no terminal to take over
+pub fn run(
+    app: &mut App,
+    out: &mut impl Write,
+    tick_rate: Duration,
+    total_ticks: u32,
+    output: PlainOutput,
+) -> io::Result<()> {
+    // there is no screen height to fit, every contributor is printed
+    if app.users == Users::Auto {
+        app.users_to_show = match output {
+            PlainOutput::Narration => NARRATED_USERS,
+            _ => usize::MAX,
+        };
+    }
+    let mut printed_week = None;
+    let mut previous_ranks: HashMap<String, usize> = HashMap::new();
+    let mut previous_leaders: Vec<(String, u32)> = Vec::new();
+
+    while app.current_tick <= total_ticks {
+        app.on_tick(total_ticks);
+        if let Some(error) = app.error.as_ref() {
+            return Err(io::Error::other(error.clone()));
+        }
+        let (Some(week), Some(authors)) = (app.current_week, app.current_tick_authors.as_ref())
+        else {
+            // the download runs on another thread, waiting for it must not spin
+            thread::sleep(tick_rate);
+            continue;
+        };
+        if printed_week == Some(week) {
+            continue;
+        }
+        printed_week = Some(week);
+
+        let date = date::convert_timestamp_to_date(&week);
+        let leaders = &authors[..authors.len().min(app.users_to_show)];
+        match output {
+            PlainOutput::Leaderboard => write!(out, "{}", leaderboard(date.as_str(), leaders))?,
+            PlainOutput::Changes => {
+                for line in rank_changes(&previous_ranks, leaders) {
+                    writeln!(out, "{}  {}", date, line)?;
+                }
+                previous_ranks = ranks(leaders);
+            }
+            PlainOutput::Narration => {
+                let month = date::convert_timestamp_to_month_name_and_year(&week);
+                for event in narration::events(&previous_leaders, leaders, app.users_to_show) {
+                    writeln!(out, "{}: {}", month, event)?;
+                }
+                previous_leaders = leaders.to_vec();
+            }
+        }
+        out.flush()?;
+        thread::sleep(tick_rate);
+    }
+    if output == PlainOutput::Narration {
+        if let Some(finish) = narration::finish(&previous_leaders, app.users_to_show) {
+            writeln!(out, "{}", finish)?;
+        }
+    }
+    Ok(())
+}
+
+fn leaderboard(date: &str, leaders: &[(String, u32)]) -> String {
+    let name_width = leaders
+        .iter()
+        .map(|(author, _)| author.chars().count())
+        .max()
+        .unwrap_or_default();
+    let mut text = format!("{}\n", date);
+    for (rank, (author, commits)) in leaders.iter().enumerate() {
+        text.push_str(&format!(
+            "{:>4}  {:<name_width$}  {}\n",
+            rank + 1,
+            author,
+            commits
+        ));
+    }
+    text.push('\n');
+    text
+}
+
+fn ranks(leaders: &[(String, u32)]) -> HashMap<String, usize> {
+    leaders
+        .iter()
+        .enumerate()
+        .map(|(rank, (author, _))| (author.clone(), rank + 1))
+        .collect()
+}
+
+/// Authors entering the leaderboard or moving inside it since the previous frame
+fn rank_changes(previous: &HashMap<String, usize>, leaders: &[(String, u32)]) -> Vec<String> {
+    leaders
+        .iter()
+        .enumerate()
+        .filter_map(|(rank, (author, _))| match previous.get(author) {
+            None => Some(format!("{} enters at #{}", author, rank + 1)),
+            Some(old) if *old != rank + 1 => Some(format!("{} #{} -> #{}", author, old, rank + 1)),
+            Some(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Download;
+    use crate::github::api::{ApiResponse, RateLimit};
+    use crate::github::contributors::contributor;
+    use std::sync::mpsc;
+
+    fn race(output: PlainOutput) -> String {
+        let mut app = App::new(vec![], Users::Auto, false);
+        app.set_contributors(vec![
+            contributor("octocat", &[3, 0, 0]),
+            contributor("hubot", &[1, 1, 5]),
+        ])
+        .unwrap();
+        let mut out = Vec::new();
+        run(&mut app, &mut out, Duration::ZERO, 3, output).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn should_print_leaderboard_of_every_week() {
+        assert_eq!(
+            race(PlainOutput::Leaderboard),
+            "2013-02-17\n   1  octocat  3\n   2  hubot    1\n\n\
+             2013-02-24\n   1  octocat  3\n   2  hubot    2\n\n\
+             2013-03-03\n   1  hubot    7\n   2  octocat  3\n\n"
+        );
+    }
+
+    #[test]
+    fn should_wait_for_the_download() {
+        let mut app = App::new(vec![], Users::Auto, false);
+        let (sender, receiver) = mpsc::channel();
+        app.download = Some(receiver);
+        let body = serde_json::to_string(&[contributor("octocat", &[3, 1])]).unwrap();
+        thread::spawn(move || {
+            sender
+                .send(Download::Retrying(Duration::from_millis(20)))
+                .unwrap();
+            thread::sleep(Duration::from_millis(20));
+            sender
+                .send(Download::Done(Ok(ApiResponse {
+                    body,
+                    rate_limit: RateLimit::default(),
+                })))
+                .unwrap();
+        });
+
+        let mut out = Vec::new();
+        run(
+            &mut app,
+            &mut out,
+            Duration::from_millis(1),
+            2,
+            PlainOutput::Leaderboard,
+        )
+        .unwrap();
+        assert!(app.download.is_none());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "2013-02-17\n   1  octocat  3\n\n2013-02-24\n   1  octocat  4\n\n"
+        );
+    }
+
+    #[test]
+    fn should_print_only_rank_changes() {
+        assert_eq!(
+            race(PlainOutput::Changes),
+            "2013-02-17  octocat enters at #1\n\
+             2013-02-17  hubot enters at #2\n\
+             2013-03-03  hubot #2 -> #1\n\
+             2013-03-03  octocat #1 -> #2\n"
+        );
+    }
+
+    #[test]
+    fn should_narrate_the_race() {
+        assert_eq!(
+            race(PlainOutput::Narration),
+            "February 2013: the race starts, octocat leads with 3 commits, ahead of hubot with 1\n\
+             March 2013: hubot overtakes octocat for the lead with 7 commits\n\
+             the race is over, hubot wins with 7 commits, ahead of octocat with 3\n"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    backend::CrosstermBackend,
+    crossterm::{
+        cursor::Show,
+        event::{DisableMouseCapture, EnableMouseCapture},
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    Terminal,
+};
+use std::io::{self, Stdout};
+use std::ops::{Deref, DerefMut};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+/// Owns the terminal while the TUI is running, raw mode and the alternate screen
+/// are restored when it is dropped, on panics and on SIGINT/SIGTERM
+pub struct TerminalGuard {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl TerminalGuard {
+    pub fn new() -> io::Result<Self> {
+        install_panic_hook();
+        // the handler can only be registered once, a second guard reuses the first one
+        let _ = ctrlc::set_handler(|| SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst));
+
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        // there is no guard yet to restore the terminal when it is dropped
+        let terminal = execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+            .and_then(|_| Terminal::new(CrosstermBackend::new(stdout)))
+            .inspect_err(|_| {
+                let _ = restore();
+            })?;
+        Ok(Self { terminal })
+    }
+}
+
+impl Deref for TerminalGuard {
+    type Target = Terminal<CrosstermBackend<Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for TerminalGuard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore();
+    }
+}
+
+/// True once a SIGINT or SIGTERM has been received
+pub fn shutdown_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+pub fn restore() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )
+}
+
+/// Restores the terminal before printing the panic, otherwise the message is lost
+/// in the alternate screen and the shell is left in raw mode. Installed once, a new
+/// guard must not wrap the hook again
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore();
+            hook(info);
+        }));
+    });
+}
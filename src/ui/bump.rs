@@ -0,0 +1,91 @@
+use crate::core::commits::Commits;
+use crate::ui::colors::Colors;
+use ratatui::symbols::Marker;
+use ratatui::text::Line;
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
+use ratatui::widgets::Borders;
+use ratatui::{layout::Rect, style::Stylize, widgets::Block, Frame};
+
+const ROLLING_WINDOW_WEEKS: usize = 12;
+
+/// Rank of the top authors at the end of every month, ranks below `users_to_show * 2`
+/// are drawn on the bottom edge
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    commits: &Commits,
+    colors: &Colors,
+    users_to_show: usize,
+    current_week: u32,
+    rolling: bool,
+) {
+    let title = if rolling {
+        format!(
+            "Rank per month, commits of the last {} weeks, press 'r' for cumulative ",
+            ROLLING_WINDOW_WEEKS
+        )
+    } else {
+        "Rank per month, cumulative commits, press 'r' for rolling window ".to_string()
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let rankings = commits.monthly_rankings(current_week, rolling.then_some(ROLLING_WINDOW_WEEKS));
+    let Some((_, latest)) = rankings.last() else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let tracked: Vec<&String> = latest.iter().take(users_to_show).map(|(a, _)| a).collect();
+    let lowest_rank = (users_to_show * 2).max(2);
+    let y = |rank: usize| (lowest_rank - rank.min(lowest_rank)) as f64;
+
+    // leave room on the right side for the labels
+    let last_month = (rankings.len() - 1).max(1) as f64;
+    let label_width = tracked.iter().map(|a| a.len() + 5).max().unwrap_or(0) as f64;
+    let plot_width = (area.width.saturating_sub(2) as f64 - label_width).max(1.0);
+    let max_x = last_month + last_month * label_width / plot_width;
+
+    let series: Vec<Vec<(f64, f64, usize)>> = tracked
+        .iter()
+        .map(|author| {
+            rankings
+                .iter()
+                .enumerate()
+                .filter_map(|(month, (_, ranked))| {
+                    let rank = ranked.iter().position(|(a, _)| a == *author)? + 1;
+                    Some((month as f64, y(rank), rank))
+                })
+                .collect()
+        })
+        .collect();
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(Marker::Braille)
+        .x_bounds([0.0, max_x])
+        .y_bounds([0.0, y(1)])
+        .paint(|ctx| {
+            for (author, points) in tracked.iter().zip(series.iter()) {
+                let color = colors.color(author);
+                for pair in points.windows(2) {
+                    if pair[1].0 - pair[0].0 > 1.0 {
+                        continue;
+                    }
+                    ctx.draw(&CanvasLine {
+                        x1: pair[0].0,
+                        y1: pair[0].1,
+                        x2: pair[1].0,
+                        y2: pair[1].1,
+                        color,
+                    });
+                }
+            }
+            ctx.layer();
+            for (author, points) in tracked.iter().zip(series.iter()) {
+                if let Some((x, y, rank)) = points.last() {
+                    let label = format!(" #{} {}", rank, author);
+                    ctx.print(*x, *y, Line::from(label).fg(colors.color(author)));
+                }
+            }
+        });
+    frame.render_widget(canvas, area);
+}
@@ -0,0 +1,324 @@
+use ratatui::style::palette::tailwind::{
+    Palette, AMBER, BLUE, CYAN, EMERALD, FUCHSIA, GREEN, INDIGO, LIME, ORANGE, PINK, PURPLE, RED,
+    ROSE, SKY, TEAL, VIOLET, YELLOW,
+};
+use ratatui::style::Color;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Hues of the racers, two racers on screen never share one while there are enough
+const PALETTES: [&Palette; 17] = [
+    &RED, &ORANGE, &AMBER, &YELLOW, &LIME, &GREEN, &EMERALD, &TEAL, &CYAN, &SKY, &BLUE, &INDIGO,
+    &VIOLET, &PURPLE, &FUCHSIA, &PINK, &ROSE,
+];
+/// Fills of the bars without colours, one per hue so racers on screen get different ones
+const FILLS: [&str; 17] = [
+    "█", "▓", "▒", "░", "#", "=", "+", "*", "~", "%", "@", "o", "x", ":", "/", "\\", "$",
+];
+/// Contrast ratio of a colour against black and against white, the WCAG minimum for
+/// graphics, so racers are readable on dark and light terminals
+const MIN_CONTRAST: f64 = 3.0;
+
+/// Shades of every hue that are readable on any background
+fn shades() -> &'static Vec<Vec<Color>> {
+    static SHADES: OnceLock<Vec<Vec<Color>>> = OnceLock::new();
+    SHADES.get_or_init(|| {
+        PALETTES
+            .iter()
+            .map(|palette| {
+                [
+                    palette.c400,
+                    palette.c500,
+                    palette.c600,
+                    palette.c700,
+                    palette.c800,
+                ]
+                .into_iter()
+                .filter(|color| {
+                    let luminance = luminance(*color);
+                    let on_black = (luminance + 0.05) / 0.05;
+                    let on_white = 1.05 / (luminance + 0.05);
+                    on_black.min(on_white) >= MIN_CONTRAST
+                })
+                .collect()
+            })
+            .collect()
+    })
+}
+
+/// Relative luminance of a RGB colour as defined by WCAG
+fn luminance(color: Color) -> f64 {
+    let Color::Rgb(r, g, b) = color else {
+        return 0.0;
+    };
+    let linear = |channel: u8| {
+        let channel = channel as f64 / 255.0;
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// Channels of the colour, named colours are approximated with the usual terminal ones
+fn rgb(color: Color) -> Option<[u8; 3]> {
+    Some(match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::Gray => [229, 229, 229],
+        Color::DarkGray => [127, 127, 127],
+        Color::LightRed => [255, 0, 0],
+        Color::LightGreen => [0, 255, 0],
+        Color::LightYellow => [255, 255, 0],
+        Color::LightBlue => [92, 92, 255],
+        Color::LightMagenta => [255, 0, 255],
+        Color::LightCyan => [0, 255, 255],
+        Color::White => [255, 255, 255],
+        _ => return None,
+    })
+}
+
+/// Hue whose shades are the closest to the colour, none for colours without channels
+fn nearest_hue(color: Color) -> Option<usize> {
+    let [r, g, b] = rgb(color)?;
+    let distance = |shade: &Color| {
+        let [sr, sg, sb] = rgb(*shade).unwrap_or_default();
+        [(r, sr), (g, sg), (b, sb)]
+            .iter()
+            .map(|(c, s)| (*c as i32 - *s as i32).pow(2))
+            .sum::<i32>()
+    };
+    shades()
+        .iter()
+        .enumerate()
+        .filter_map(|(hue, shades)| Some((hue, shades.iter().map(distance).min()?)))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(hue, _)| hue)
+}
+
+/// FNV-1a, unlike `DefaultHasher` its output never changes between Rust versions
+fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Hue and shade the author gets when nobody else on screen has them
+fn preferred(author: &str) -> (usize, usize) {
+    let hash = stable_hash(author);
+    let hue = (hash % PALETTES.len() as u64) as usize;
+    let shade = (hash / PALETTES.len() as u64) % shades()[hue].len() as u64;
+    (hue, shade as usize)
+}
+
+/// Colour of the author without looking at the other racers
+pub fn stable_color(author: &str) -> Color {
+    let (hue, shade) = preferred(author);
+    shades()[hue][shade]
+}
+
+/// Colours of the racers. A racer keeps its colour from a frame to the next, and the
+/// racers on screen get different hues. `overrides` are the colours chosen by the user
+#[derive(Clone, Debug, Default)]
+pub struct Colors {
+    overrides: HashMap<String, Color>,
+    assigned: HashMap<String, (usize, Color)>,
+    visible: Vec<String>,
+    monochrome: bool,
+}
+
+impl Colors {
+    pub fn new(overrides: HashMap<String, Color>) -> Self {
+        Colors {
+            overrides,
+            ..Colors::default()
+        }
+    }
+
+    /// Racers without colours, they are told apart by the fill of their bars
+    pub fn monochrome(self) -> Self {
+        Colors {
+            overrides: HashMap::new(),
+            monochrome: true,
+            ..self
+        }
+    }
+
+    /// Colour given to the author, its stable colour when it never was on screen
+    pub fn color(&self, author: &str) -> Color {
+        if self.monochrome {
+            return Color::Reset;
+        }
+        self.overrides
+            .get(author)
+            .or_else(|| self.assigned.get(author).map(|(_, color)| color))
+            .copied()
+            .unwrap_or_else(|| stable_color(author))
+    }
+
+    /// Symbol the bars of the author are filled with
+    pub fn fill(&self, author: &str) -> &'static str {
+        if !self.monochrome {
+            return FILLS[0];
+        }
+        let hue = self
+            .assigned
+            .get(author)
+            .map_or_else(|| preferred(author).0, |(hue, _)| *hue);
+        FILLS[hue % FILLS.len()]
+    }
+
+    /// Gives colours to the racers on screen, the ones already there keep theirs
+    pub fn allocate(&mut self, visible: &[&str]) {
+        let mut colors: Vec<Color> = visible
+            .iter()
+            .filter_map(|author| self.overrides.get(*author).copied())
+            .collect();
+        // the colours chosen by the user take the hue they look like
+        let mut hues: Vec<usize> = colors.iter().filter_map(|c| nearest_hue(*c)).collect();
+        let (staying, arriving): (Vec<&str>, Vec<&str>) = visible
+            .iter()
+            .filter(|author| !self.overrides.contains_key(**author))
+            .partition(|author| self.visible.iter().any(|v| v == *author));
+        for author in staying {
+            if let Some((hue, color)) = self.assigned.get(author) {
+                hues.push(*hue);
+                colors.push(*color);
+            }
+        }
+        for author in arriving {
+            let (hue, color) = match self.assigned.get(author) {
+                // a racer coming back keeps its colour if nobody on screen took the hue
+                Some((hue, color)) if !hues.contains(hue) && !colors.contains(color) => {
+                    (*hue, *color)
+                }
+                _ => self.pick(author, &hues, &colors),
+            };
+            hues.push(hue);
+            colors.push(color);
+            self.assigned.insert(author.to_string(), (hue, color));
+        }
+        self.visible = visible.iter().map(|author| author.to_string()).collect();
+    }
+
+    /// First free hue from the preferred one, hues that no racer ever had come first so
+    /// the racers off screen can come back with their colour
+    fn pick(&self, author: &str, hues: &[usize], colors: &[Color]) -> (usize, Color) {
+        let (preferred_hue, shade) = preferred(author);
+        let shades = shades();
+        let candidates =
+            (0..PALETTES.len()).map(|offset| (preferred_hue + offset) % PALETTES.len());
+        let color = |hue: usize| shades[hue][shade % shades[hue].len()];
+        let unused = |hue: &usize| !hues.contains(hue) && !colors.contains(&color(*hue));
+        let never_assigned = |hue: &usize| self.assigned.values().all(|(h, _)| h != hue);
+        if unused(&preferred_hue) {
+            return (preferred_hue, color(preferred_hue));
+        }
+        if let Some(hue) = candidates
+            .clone()
+            .find(|hue| unused(hue) && never_assigned(hue))
+            .or_else(|| candidates.clone().find(unused))
+        {
+            return (hue, color(hue));
+        }
+        // more racers than hues, at least the exact colour differs
+        candidates
+            .flat_map(|hue| shades[hue].iter().map(move |color| (hue, *color)))
+            .find(|(_, color)| !colors.contains(color))
+            .unwrap_or((preferred_hue, color(preferred_hue)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_readable_shades() {
+        for shades in shades() {
+            assert!(!shades.is_empty());
+            for color in shades {
+                let luminance = luminance(*color);
+                assert!((0.1..=0.3).contains(&luminance), "{}", color);
+            }
+        }
+        // the hash is part of the output, it must not change between builds
+        assert_eq!(stable_hash("octocat"), 0x3b73d0f179967e56);
+    }
+
+    #[test]
+    fn should_give_different_hues_to_visible_racers() {
+        let authors: Vec<String> = (0..PALETTES.len()).map(|i| format!("user{}", i)).collect();
+        let visible: Vec<&str> = authors.iter().map(String::as_str).collect();
+        let mut colors = Colors::default();
+        colors.allocate(&visible);
+
+        let mut hues: Vec<usize> = colors.assigned.values().map(|(hue, _)| *hue).collect();
+        hues.sort();
+        hues.dedup();
+        assert_eq!(hues.len(), PALETTES.len());
+
+        // one more racer than hues still gets its own colour
+        colors.allocate(&[visible.as_slice(), &["late"]].concat());
+        let mut all: Vec<Color> = colors.assigned.values().map(|(_, color)| *color).collect();
+        all.sort_by_key(|color| color.to_string());
+        all.dedup();
+        assert_eq!(all.len(), PALETTES.len() + 1);
+    }
+
+    #[test]
+    fn should_keep_colors_between_frames() {
+        let mut colors = Colors::new(HashMap::from([("hubot".to_string(), Color::Rgb(1, 2, 3))]));
+        colors.allocate(&["octocat", "hubot"]);
+        let octocat = colors.color("octocat");
+        assert_eq!(octocat, stable_color("octocat"));
+        assert_eq!(colors.color("hubot"), Color::Rgb(1, 2, 3));
+
+        colors.allocate(&["monalisa", "octocat"]);
+        assert_eq!(colors.color("octocat"), octocat);
+        assert_ne!(colors.color("monalisa"), octocat);
+        assert_eq!(colors.color("nobody"), stable_color("nobody"));
+        assert_eq!(colors.fill("octocat"), "█");
+    }
+
+    #[test]
+    fn should_reserve_the_hue_of_overrides() {
+        assert_eq!(nearest_hue(Color::Rgb(240, 100, 20)), Some(1));
+        assert_eq!(nearest_hue(Color::Red), Some(0));
+        assert_eq!(nearest_hue(Color::Reset), None);
+
+        // a racer preferring orange makes way for the orange chosen by the user
+        let authors: Vec<String> = (0..100).map(|i| format!("user{}", i)).collect();
+        let orange = authors.iter().find(|a| preferred(a).0 == 1).unwrap();
+        let mut colors = Colors::new(HashMap::from([(
+            "hubot".to_string(),
+            Color::Rgb(240, 100, 20),
+        )]));
+        colors.allocate(&["hubot", orange]);
+        assert_ne!(colors.assigned[orange.as_str()].0, 1);
+    }
+
+    #[test]
+    fn should_fill_bars_without_colours() {
+        let mut colors =
+            Colors::new(HashMap::from([("hubot".to_string(), Color::Red)])).monochrome();
+        colors.allocate(&["octocat", "hubot", "monalisa"]);
+        assert_eq!(colors.color("hubot"), Color::Reset);
+        let mut fills = vec![
+            colors.fill("octocat"),
+            colors.fill("hubot"),
+            colors.fill("monalisa"),
+        ];
+        fills.sort();
+        fills.dedup();
+        assert_eq!(fills.len(), 3);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::core::commits::Commits;
+use crate::ui::colors::Colors;
+use crate::ui::theme::Theme;
+use crate::utils::date;
+use ratatui::style::Stylize;
+use ratatui::symbols::Marker;
+use ratatui::text::Line;
+use ratatui::widgets::{Axis, Borders, Chart, Dataset, GraphType, Sparkline};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    commits: &Commits,
+    colors: &Colors,
+    theme: &Theme,
+    author: &str,
+    current_week: u32,
+) {
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(6),
+        Constraint::Min(6),
+        Constraint::Min(6),
+    ])
+    .spacing(1)
+    .split(area);
+
+    let color = colors.color(author);
+    let weeks = commits.author_weeks(author, current_week);
+    let ranks = commits.rank_history(author, current_week);
+    let total = weeks.last().map_or(0, |w| w.total);
+    let rank = ranks
+        .last()
+        .map_or("-".to_string(), |(_, r)| format!("#{}", r));
+
+    let summary = Paragraph::new(Line::from(vec![
+        author.to_string().bold().fg(color),
+        format!(" {} commits, rank {}", total, rank).into(),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Contributor details, press 'esc' to go back "),
+    );
+    frame.render_widget(summary, chunks[0]);
+
+    let weekly: Vec<u64> = weeks.iter().map(|w| w.commits as u64).collect();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Weekly commits"),
+        )
+        .data(fit_to_width(
+            &weekly,
+            chunks[1].width.saturating_sub(2) as usize,
+        ))
+        .style(Style::default().fg(color));
+    frame.render_widget(sparkline, chunks[1]);
+
+    let (first_week, last_week) = match (weeks.first(), weeks.last()) {
+        (Some(first), Some(last)) => (first.week as f64, last.week as f64),
+        _ => return,
+    };
+    let x_axis = || {
+        Axis::default()
+            .bounds([first_week, last_week.max(first_week + 1.0)])
+            .labels([
+                date::convert_timestamp_to_month_and_year(&(first_week as u32)),
+                date::convert_timestamp_to_month_and_year(&(last_week as u32)),
+            ])
+    };
+
+    let additions: Vec<(f64, f64)> = weeks
+        .iter()
+        .map(|w| (w.week as f64, w.additions as f64))
+        .collect();
+    let deletions: Vec<(f64, f64)> = weeks
+        .iter()
+        .map(|w| (w.week as f64, w.deletions as f64))
+        .collect();
+    let max_lines = weeks
+        .iter()
+        .map(|w| w.additions.max(w.deletions))
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let lines_chart = Chart::new(vec![
+        Dataset::default()
+            .name("additions")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(theme.additions)
+            .data(&additions),
+        Dataset::default()
+            .name("deletions")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(theme.deletions)
+            .data(&deletions),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Additions / deletions"),
+    )
+    .x_axis(x_axis())
+    .y_axis(
+        Axis::default()
+            .bounds([0.0, max_lines])
+            .labels(["0".to_string(), (max_lines as u32).to_string()]),
+    );
+    frame.render_widget(lines_chart, chunks[2]);
+
+    // ranks are drawn upside down so the leader (#1) sits on top of the chart
+    let worst_rank = ranks.iter().map(|(_, r)| *r).max().unwrap_or(1).max(2) as f64;
+    let rank_points: Vec<(f64, f64)> = ranks
+        .iter()
+        .map(|(week, r)| (*week as f64, worst_rank + 1.0 - *r as f64))
+        .collect();
+    let rank_chart = Chart::new(vec![Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(&rank_points)])
+    .block(Block::default().borders(Borders::ALL).title("Rank history"))
+    .x_axis(x_axis())
+    .y_axis(
+        Axis::default()
+            .bounds([1.0, worst_rank])
+            .labels([format!("#{}", worst_rank as u32), "#1".to_string()]),
+    );
+    frame.render_widget(rank_chart, chunks[3]);
+}
+
+/// Sums consecutive values so the whole history fits in `width` columns
+fn fit_to_width(data: &[u64], width: usize) -> Vec<u64> {
+    if width == 0 || data.len() <= width {
+        return data.to_vec();
+    }
+    let bucket = data.len().div_ceil(width);
+    data.chunks(bucket).map(|c| c.iter().sum()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_fit_data_to_width() {
+        assert_eq!(fit_to_width(&[1, 2, 3], 5), vec![1, 2, 3]);
+        assert_eq!(fit_to_width(&[1, 2, 3, 4, 5], 2), vec![6, 9]);
+        assert_eq!(fit_to_width(&[1, 2], 0), vec![1, 2]);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::core::commits::Commits;
+use crate::ui::theme::Theme;
+use crate::utils::date;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Borders, Paragraph};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Block,
+    Frame,
+};
+use std::collections::BTreeMap;
+
+const WEEKS_PER_YEAR: usize = 53;
+/// No commits and the four quarters of the busiest week
+const BUCKETS: usize = 5;
+
+/// Calendar of weekly commits, one row per year and one column per week of the year
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    commits: &Commits,
+    theme: &Theme,
+    author: Option<&str>,
+    current_week: u32,
+) {
+    let totals = commits.weekly_totals(author, current_week);
+    let max = totals.iter().map(|(_, c)| *c).max().unwrap_or(0);
+
+    let mut years: BTreeMap<i32, [Option<u32>; WEEKS_PER_YEAR]> = BTreeMap::new();
+    for (week, count) in totals.iter() {
+        let (year, week_of_year) = date::year_week(week);
+        let cells = years.entry(year).or_insert([None; WEEKS_PER_YEAR]);
+        let cell = &mut cells[week_of_year as usize];
+        *cell = Some(cell.unwrap_or(0) + count);
+    }
+
+    let mut lines = vec![months_header()];
+    for (year, cells) in years.iter() {
+        let mut spans = vec![Span::raw(format!("{} ", year))];
+        spans.extend(cells.iter().map(|week| match week {
+            Some(count) => cell(theme.heatmap[bucket(*count, max)]),
+            None => Span::raw(" "),
+        }));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::raw(""));
+    let mut legend = vec![Span::raw("less ")];
+    legend.extend(theme.heatmap.iter().map(|bucket| cell(*bucket)));
+    legend.push(Span::raw(" more"));
+    lines.push(Line::from(legend));
+
+    let title = format!(
+        "Commit heatmap for {}, ↑/↓ to select a contributor ",
+        author.unwrap_or("all contributors")
+    );
+    let heatmap = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(heatmap, area);
+}
+
+/// Month names over the first week of every quarter, after the year column
+fn months_header() -> Line<'static> {
+    let quarters: String = ["Jan", "Apr", "Jul", "Oct"]
+        .iter()
+        .map(|month| format!("{:<13}", month))
+        .collect();
+    Line::raw(format!("     {}", quarters))
+}
+
+/// Splits the commits in quarters of the busiest week, like Github does
+fn bucket(count: u32, max: u32) -> usize {
+    if count == 0 || max == 0 {
+        return 0;
+    }
+    let quarter = (count as f64 / max as f64 * 4.0).ceil() as usize;
+    quarter.clamp(1, BUCKETS - 1)
+}
+
+fn cell((symbol, color): (&'static str, Color)) -> Span<'static> {
+    Span::styled(symbol, Style::default().fg(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_bucket_commits_by_quarter_of_max() {
+        assert_eq!(bucket(0, 0), 0);
+        assert_eq!(bucket(0, 10), 0);
+        assert_eq!(bucket(1, 10), 1);
+        assert_eq!(bucket(5, 10), 2);
+        assert_eq!(bucket(7, 10), 3);
+        assert_eq!(bucket(10, 10), 4);
+    }
+}
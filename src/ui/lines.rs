@@ -0,0 +1,126 @@
+use crate::core::commits::Commits;
+use crate::ui::colors::Colors;
+use crate::utils::date;
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Borders, Chart, Dataset, GraphType, LegendPosition};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Style,
+    widgets::Block,
+    Frame,
+};
+
+/// Points of every author and the bounds of the axes, weeks on x and commits on y
+struct Series {
+    points: Vec<Vec<(f64, f64)>>,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+}
+
+fn series(commits: &Commits, top_users: &[(String, u32)], current_week: u32) -> Series {
+    let first_week = commits
+        .sorted_weeks()
+        .first()
+        .copied()
+        .unwrap_or(current_week) as f64;
+    let last_week = (current_week as f64).max(first_week + 1.0);
+    let max_commits = top_users.first().map_or(0, |(_, c)| *c).max(1) as f64;
+
+    let points = top_users
+        .iter()
+        .map(|(author, _)| {
+            commits
+                .author_weeks(author, current_week)
+                .iter()
+                .map(|w| (w.week as f64, w.total as f64))
+                .collect()
+        })
+        .collect();
+    Series {
+        points,
+        x_bounds: [first_week, last_week],
+        y_bounds: [0.0, max_commits],
+    }
+}
+
+/// Cumulative commits of the top authors, drawn up to the current week
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    commits: &Commits,
+    colors: &Colors,
+    top_users: &[(String, u32)],
+    current_week: u32,
+) {
+    let series = series(commits, top_users, current_week);
+    let datasets = top_users
+        .iter()
+        .zip(series.points.iter())
+        .map(|((author, _), data)| {
+            Dataset::default()
+                .name(author.as_str())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(colors.color(author)))
+                .data(data)
+        })
+        .collect();
+
+    let [first_week, _] = series.x_bounds;
+    let [_, max_commits] = series.y_bounds;
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Cumulative commits"),
+        )
+        .legend_position(Some(LegendPosition::TopLeft))
+        .hidden_legend_constraints((Constraint::Percentage(50), Constraint::Percentage(50)))
+        .x_axis(Axis::default().bounds(series.x_bounds).labels([
+            date::convert_timestamp_to_month_and_year(&(first_week as u32)),
+            date::convert_timestamp_to_month_and_year(&current_week),
+        ]))
+        .y_axis(
+            Axis::default()
+                .bounds(series.y_bounds)
+                .labels(["0".to_string(), (max_commits as u32).to_string()]),
+        );
+    frame.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::commits::get_commits_per_week;
+    use crate::github::contributors::contributor;
+
+    #[test]
+    fn should_compute_series_up_to_current_week() {
+        let commits = get_commits_per_week(
+            vec![
+                contributor("octocat", &[2, 0, 3]),
+                contributor("hubot", &[1, 1, 0]),
+            ],
+            vec![],
+        )
+        .unwrap();
+        let second_week = 1361059200 + 604800;
+        let top_users = vec![("octocat".to_string(), 2), ("hubot".to_string(), 2)];
+
+        let lines = series(&commits, &top_users, second_week);
+        assert_eq!(
+            lines.points,
+            vec![
+                vec![(1361059200.0, 2.0), (second_week as f64, 2.0)],
+                vec![(1361059200.0, 1.0), (second_week as f64, 2.0)],
+            ]
+        );
+        assert_eq!(lines.x_bounds, [1361059200.0, second_week as f64]);
+        assert_eq!(lines.y_bounds, [0.0, 2.0]);
+
+        // a single week still gets a range, and a leader without commits a height
+        let first_week = series(&commits, &[("nobody".to_string(), 0)], 1361059200);
+        assert_eq!(first_week.x_bounds, [1361059200.0, 1361059201.0]);
+        assert_eq!(first_week.y_bounds, [0.0, 1.0]);
+    }
+}
@@ -0,0 +1,446 @@
+use crate::app::{App, InputMode, ViewMode};
+use crate::github::api::RateLimit;
+use crate::utils::date;
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Borders, Padding, Widget};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Gauge, Paragraph, Wrap},
+    Frame,
+};
+
+mod bump;
+pub mod colors;
+mod detail;
+mod heatmap;
+mod lines;
+#[cfg(test)]
+mod snapshots;
+pub mod theme;
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(1),
+    ])
+    .spacing(1)
+    .margin(1)
+    .split(frame.area());
+    let row_height = if app.compact { 1 } else { 6 };
+    app.fit_users_to_rows((chunks[2].height / row_height) as usize);
+    if let Some(authors) = app.current_tick_authors.as_ref() {
+        let visible: Vec<&str> = authors
+            .iter()
+            .take(app.users_to_show)
+            .map(|(author, _)| author.as_str())
+            .collect();
+        app.colors.allocate(&visible);
+    }
+
+    match app.input_mode {
+        InputMode::Normal => {
+            if app.commits.is_none() {
+                let text = match app.retry_in() {
+                    Some(delay) => format!(
+                        "Github is busy, retrying in {}s...",
+                        delay.as_secs_f64().ceil() as u64
+                    ),
+                    None => "loading repository data...".to_string(),
+                };
+                let loading = Paragraph::new(text).style(Style::default());
+                frame.render_widget(loading, chunks[1]);
+            }
+            if let Some(current_week) = app.current_week {
+                let current_week = Paragraph::new(
+                    date::convert_timestamp_to_month_and_year(&current_week).to_string(),
+                )
+                .style(app.theme.date)
+                .block(Block::default().borders(Borders::BOTTOM));
+                frame.render_widget(current_week, chunks[1]);
+            }
+            if let Some(quota) = app.rate_limit.as_ref().and_then(rate_limit_text) {
+                let quota = Paragraph::new(quota).style(app.theme.quota).right_aligned();
+                frame.render_widget(quota, chunks[1]);
+            }
+        }
+        InputMode::Editing => {
+            frame.set_cursor_position((
+                chunks[1].x + app.input.visual_cursor() as u16 + 1,
+                chunks[1].y + 1,
+            ));
+            let input = Paragraph::new(app.input.value())
+                .style(app.theme.input)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Github repository, ie: 'rust-lang/rust'"),
+                )
+                .slow_blink();
+            frame.render_widget(input, chunks[1]);
+        }
+    }
+    let greeting =
+        Paragraph::new("")
+            .style(app.theme.header)
+            .block(Block::default().borders(Borders::BOTTOM).title(
+                "Github commit race, press 'q' to quit, 'v' to change view, 'c' for compact bars, ↑/↓ to select, 'enter' for details ",
+            ));
+    frame.render_widget(greeting, chunks[0]);
+    if let Some(error) = app.error.as_ref() {
+        let error = Paragraph::new(error.as_str())
+            .style(app.theme.error)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title("Error"));
+        frame.render_widget(error, chunks[2]);
+    }
+    if let (Some(author), Some(commits), Some(current_week)) = (
+        app.detail_author.as_ref(),
+        app.commits.as_ref(),
+        app.current_week,
+    ) {
+        detail::draw(
+            frame,
+            chunks[2],
+            commits,
+            &app.colors,
+            &app.theme,
+            author,
+            current_week,
+        );
+        return;
+    }
+    if app.view_mode != ViewMode::Bars {
+        app.bar_areas.clear();
+        if let (Some(authors), Some(commits), Some(current_week)) = (
+            app.current_tick_authors.as_ref(),
+            app.commits.as_ref(),
+            app.current_week,
+        ) {
+            let top_users = &authors[0..app.users_to_show.min(authors.len())];
+            match app.view_mode {
+                ViewMode::Lines => lines::draw(
+                    frame,
+                    chunks[2],
+                    commits,
+                    &app.colors,
+                    top_users,
+                    current_week,
+                ),
+                ViewMode::Bump => bump::draw(
+                    frame,
+                    chunks[2],
+                    commits,
+                    &app.colors,
+                    app.users_to_show,
+                    current_week,
+                    app.rolling_ranks,
+                ),
+                ViewMode::Heatmap => heatmap::draw(
+                    frame,
+                    chunks[2],
+                    commits,
+                    &app.theme,
+                    app.selected_author(),
+                    current_week,
+                ),
+                ViewMode::Bars => {}
+            }
+        }
+        return;
+    }
+    if let Some(authors) = app.current_tick_authors.as_ref() {
+        let constraints: Vec<Constraint> = (0..app.users_to_show)
+            .map(|_| Constraint::Length(row_height)) // Each area will have a height of 6, or 1 in compact mode
+            .collect();
+
+        let layout = Layout::vertical(constraints);
+        let areas = layout.split(chunks[2]);
+
+        let top_users = authors[0..app.users_to_show.min(authors.len())].to_vec();
+        app.bar_areas = areas[0..top_users.len()].to_vec();
+        let name_width = top_users
+            .iter()
+            .map(|(a, _)| a.chars().count())
+            .max()
+            .unwrap_or(0);
+        let leader_commits = top_users.first().map_or(0, |(_, c)| *c);
+        for (i, author_with_commit) in top_users.iter().enumerate() {
+            let selected = app.selected_author() == Some(author_with_commit.0.as_str());
+            let ratio = bar_ratio(author_with_commit.1, leader_commits);
+            let color = app.colors.color(author_with_commit.0.as_str());
+            let fill = app.colors.fill(author_with_commit.0.as_str());
+            if app.compact {
+                render_compact_bar(
+                    author_with_commit,
+                    (color, fill),
+                    ratio,
+                    selected,
+                    name_width,
+                    areas[i],
+                    frame,
+                );
+            } else {
+                render_gauge(
+                    author_with_commit,
+                    (color, fill),
+                    app.theme.label,
+                    ratio,
+                    selected,
+                    areas[i],
+                    frame,
+                );
+            }
+        }
+        // keep the layout stable when the repository has less contributors than requested
+        for area in areas.iter().skip(top_users.len()) {
+            render_placeholder(app.compact, app.theme.placeholder, *area, frame);
+        }
+    }
+
+    fn render_placeholder(compact: bool, style: Style, area: Rect, frame: &mut Frame) {
+        if compact {
+            Paragraph::new("  -")
+                .style(style)
+                .render(area, frame.buffer_mut());
+        } else {
+            Gauge::default()
+                .block(title_block("-", false).style(style))
+                .gauge_style(style)
+                .ratio(0.0)
+                .label("")
+                .render(area, frame.buffer_mut());
+        }
+    }
+
+    /// Bars are told apart by their colour, or by their fill when there are no colours
+    fn render_compact_bar(
+        author: &(String, u32),
+        (color, fill): (Color, &str),
+        ratio: f64,
+        selected: bool,
+        name_width: usize,
+        area: Rect,
+        frame: &mut Frame,
+    ) {
+        let marker = if selected { "▶ " } else { "  " };
+        let mut name = Span::from(format!("{}{:<name_width$} ", marker, author.0)).fg(color);
+        if selected {
+            name = name.bold();
+        }
+        let value = format!(" {}", author.1);
+        let bar_width = (area.width as usize).saturating_sub(name_width + 3 + value.len());
+        let bar = fill.repeat((bar_width as f64 * ratio).round() as usize);
+        Paragraph::new(Line::from(vec![
+            name,
+            Span::from(bar).fg(color),
+            Span::from(value),
+        ]))
+        .render(area, frame.buffer_mut());
+    }
+
+    fn render_gauge(
+        author: &(String, u32),
+        (color, fill): (Color, &str),
+        label: Style,
+        ratio: f64,
+        selected: bool,
+        area: Rect,
+        frame: &mut Frame,
+    ) {
+        let title = title_block(author.0.as_str(), selected).fg(color);
+        let bar = title.inner(area);
+        Gauge::default()
+            .block(title)
+            .gauge_style(color)
+            .ratio(ratio)
+            .label(Span::styled(author.1.to_string(), label))
+            .render(area, frame.buffer_mut());
+        // the gauge always fills with full blocks
+        if fill != "█" {
+            let buffer = frame.buffer_mut();
+            // only the bar, a login may contain full blocks
+            for position in bar.positions() {
+                if buffer[position].symbol() == "█" {
+                    buffer[position].set_symbol(fill);
+                }
+            }
+        }
+    }
+
+    fn title_block(username: &str, selected: bool) -> Block<'_> {
+        let title = if selected {
+            Line::from(format!("▶ {} ◀", username)).bold().centered()
+        } else {
+            Line::from(username).centered()
+        };
+        Block::new()
+            .borders(Borders::NONE)
+            .padding(Padding::vertical(1))
+            .title(title)
+    }
+}
+
+fn rate_limit_text(rate_limit: &RateLimit) -> Option<String> {
+    let remaining = rate_limit.remaining?;
+    Some(match rate_limit.reset {
+        Some(reset) => format!(
+            "Github quota: {} left, resets at {}",
+            remaining,
+            date::convert_timestamp_to_time(&reset)
+        ),
+        None => format!("Github quota: {} left", remaining),
+    })
+}
+
+/// Size of the bar compared to the leader, 0 when nobody has commits yet
+fn bar_ratio(commits: u32, leader_commits: u32) -> f64 {
+    if leader_commits == 0 {
+        0.0
+    } else {
+        (commits as f64 / leader_commits as f64).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{Download, Users};
+    use crate::github::api::ApiResponse;
+    use crate::github::contributors::{contributor, Contributor};
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::time::Duration;
+
+    fn app_with(contributors: Vec<Contributor>, users: usize, compact: bool) -> App {
+        let mut app = App::new(Vec::new(), Users::Count(users), compact);
+        app.input_mode = InputMode::Normal;
+        app.set_contributors(contributors).unwrap();
+        app
+    }
+
+    fn render(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn should_render_placeholders_when_less_contributors_than_users() {
+        let mut app = app_with(vec![contributor("octocat", &[1, 2])], 3, false);
+        app.on_tick(10);
+
+        let lines = render(&mut app, 40, 30);
+        assert_eq!(app.bar_areas.len(), 1);
+        assert!(lines.iter().any(|l| l.trim() == "octocat"));
+        assert_eq!(lines.iter().filter(|l| l.trim() == "-").count(), 2);
+    }
+
+    #[test]
+    fn should_render_empty_bars_when_leader_has_no_commits() {
+        let mut app = app_with(
+            vec![
+                contributor("octocat", &[0, 2]),
+                contributor("octobot", &[0, 1]),
+            ],
+            2,
+            true,
+        );
+        app.on_tick(10);
+
+        let lines = render(&mut app, 40, 12);
+        let octocat = lines.iter().find(|l| l.contains("octocat")).unwrap();
+        assert!(!octocat.contains('█'));
+        assert!(octocat.trim_end().ends_with(" 0"));
+    }
+
+    #[test]
+    fn should_render_error_without_weeks() {
+        let mut app = App::new(Vec::new(), Users::Count(5), false);
+        let error = app.set_contributors(vec![contributor("octocat", &[])]);
+        app.error = error.err().map(|e| e.to_string());
+        app.on_tick(10);
+
+        let lines = render(&mut app, 60, 12);
+        assert!(app.current_week.is_none());
+        assert!(lines.iter().any(|l| l.contains("Error")));
+        assert!(lines.iter().any(|l| l.contains("No commits to race")));
+    }
+
+    #[test]
+    fn should_keep_the_selected_racer_when_ranks_change() {
+        let mut app = app_with(
+            vec![
+                contributor("octocat", &[2, 0]),
+                contributor("hubot", &[1, 5]),
+            ],
+            2,
+            true,
+        );
+        app.on_tick(1);
+        app.on_down();
+        app.on_down();
+        assert_eq!(app.selected_author(), Some("hubot"));
+        assert_eq!(app.selected_index(), Some(1));
+
+        // hubot takes the lead, the heatmap and the highlighted bar follow it
+        app.on_tick(1);
+        assert_eq!(app.selected_author(), Some("hubot"));
+        assert_eq!(app.selected_index(), Some(0));
+        let lines = render(&mut app, 40, 12);
+        assert!(lines.iter().any(|l| l.starts_with(" ▶ hubot")));
+    }
+
+    #[test]
+    fn should_show_retry_countdown_while_downloading() {
+        let mut app = App::new(Vec::new(), Users::Count(2), false);
+        app.input_mode = InputMode::Normal;
+        let (sender, receiver) = std::sync::mpsc::channel();
+        app.download = Some(receiver);
+
+        sender
+            .send(Download::Retrying(Duration::from_secs(30)))
+            .unwrap();
+        app.on_tick(10);
+        let lines = render(&mut app, 60, 12);
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("Github is busy, retrying in 30s...")));
+
+        let body = serde_json::to_string(&[contributor("octocat", &[1, 2])]).unwrap();
+        sender
+            .send(Download::Done(Ok(ApiResponse {
+                body,
+                rate_limit: RateLimit::default(),
+            })))
+            .unwrap();
+        app.on_tick(10);
+        assert!(app.download.is_none());
+        assert!(app.retry_in().is_none());
+        assert!(app.commits.is_some());
+    }
+
+    #[test]
+    fn should_fill_only_the_bar_without_colours() {
+        let mut app = app_with(vec![contributor("█bot", &[1, 2])], 1, false);
+        app.colors = app.colors.clone().monochrome();
+        app.on_tick(10);
+        let fill = app.colors.fill("█bot");
+        assert_ne!(fill, "█");
+
+        let lines = render(&mut app, 40, 12);
+        assert!(lines.iter().any(|l| l.trim() == "█bot"));
+        assert!(lines.iter().any(|l| l.contains(fill)));
+    }
+
+    #[test]
+    fn should_compute_bar_ratio() {
+        assert_eq!(bar_ratio(0, 0), 0.0);
+        assert_eq!(bar_ratio(5, 10), 0.5);
+        assert_eq!(bar_ratio(10, 10), 1.0);
+    }
+}
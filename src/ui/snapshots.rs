@@ -0,0 +1,116 @@
+//! Renders whole screens and compares them with the snapshots stored in `snapshots/`,
+//! the symbols and the styles of every cell. After an intended change of the UI run
+//! `UPDATE_SNAPSHOTS=1 cargo test` to rewrite them and review the diff
+
+use super::draw;
+use super::theme::ThemeName;
+use crate::app::{App, InputMode, Users};
+use crate::github::contributors::serialize_contributors;
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::collections::HashMap;
+use std::path::Path;
+use std::{env, fs};
+use tui_input::Input;
+
+const WIDTH: u16 = 60;
+const HEIGHT: u16 = 24;
+/// One tick per week of the fixture
+const TOTAL_TICKS: u32 = 6;
+
+fn fixture_app(compact: bool) -> App {
+    let contributors = serialize_contributors(include_str!("snapshots/contributors.json"));
+    let mut app = App::new(Vec::new(), Users::Auto, compact);
+    app.set_contributors(contributors.unwrap()).unwrap();
+    app.input_mode = InputMode::Normal;
+    app
+}
+
+fn render_after_ticks(app: &mut App, ticks: u32) -> Buffer {
+    for _ in 0..ticks {
+        app.on_tick(TOTAL_TICKS);
+    }
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| draw(frame, app)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{}.snap", name));
+    let actual = format!("{:?}\n", buffer);
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no snapshot at {}, run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert_eq!(
+        actual, expected,
+        "'{}' does not match its snapshot, run with UPDATE_SNAPSHOTS=1 if the change is intended",
+        name
+    );
+}
+
+#[test]
+fn should_render_editing_mode() {
+    let mut app = App::new(Vec::new(), Users::Auto, false);
+    app.input = Input::new("rust-lang/ru".to_string());
+    assert_snapshot("editing", &render_after_ticks(&mut app, 0));
+}
+
+#[test]
+fn should_render_invalid_repository_error() {
+    let mut app = App::new(Vec::new(), Users::Auto, false);
+    app.input = Input::new("rust-lang".to_string());
+    app.load_repository("rust-lang");
+    assert_snapshot("invalid_repository", &render_after_ticks(&mut app, 0));
+}
+
+#[test]
+fn should_render_loading() {
+    let mut app = App::new(Vec::new(), Users::Auto, false);
+    app.input_mode = InputMode::Normal;
+    assert_snapshot("loading", &render_after_ticks(&mut app, 0));
+}
+
+#[test]
+fn should_render_empty_data_error() {
+    let mut app = App::new(Vec::new(), Users::Auto, false);
+    let error = app.set_contributors(serialize_contributors("[]").unwrap());
+    app.error = error.err().map(|e| e.to_string());
+    assert_snapshot("empty_data", &render_after_ticks(&mut app, 1));
+}
+
+#[test]
+fn should_render_race_frames() {
+    let mut app = fixture_app(false);
+    assert_snapshot("race_start", &render_after_ticks(&mut app, 1));
+    assert_snapshot("race_middle", &render_after_ticks(&mut app, 2));
+    // the ticks after the last one leave the final standings on screen
+    assert_snapshot("race_end", &render_after_ticks(&mut app, TOTAL_TICKS + 2));
+}
+
+#[test]
+fn should_render_compact_race() {
+    let mut app = fixture_app(true);
+    assert_snapshot("compact_race_middle", &render_after_ticks(&mut app, 3));
+}
+
+#[test]
+fn should_render_race_without_colours() {
+    let mut app = fixture_app(false);
+    app.set_theme(ThemeName::Monochrome.theme(), HashMap::new());
+    assert_snapshot("monochrome_race_middle", &render_after_ticks(&mut app, 3));
+
+    let mut app = fixture_app(true);
+    app.set_theme(ThemeName::Monochrome.theme(), HashMap::new());
+    assert_snapshot(
+        "monochrome_compact_race_middle",
+        &render_after_ticks(&mut app, 3),
+    );
+}
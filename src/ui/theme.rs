@@ -0,0 +1,176 @@
+use clap::ValueEnum;
+use ratatui::style::palette::tailwind::{AMBER, BLUE, GREEN, RED, SLATE};
+use ratatui::style::{Color, Modifier, Style};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ThemeName {
+    /// Bright text for dark terminals
+    #[default]
+    Dark,
+    /// Darker text for light terminals
+    Light,
+    /// Bold and bright text
+    HighContrast,
+    /// Colours of the Solarized palette
+    Solarized,
+    /// No colours, racers are told apart by the fill of their bars
+    Monochrome,
+}
+
+/// Styles of everything on screen that is not a racer
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub header: Style,
+    pub date: Style,
+    pub quota: Style,
+    pub input: Style,
+    pub error: Style,
+    pub placeholder: Style,
+    /// Commits written on the bars
+    pub label: Style,
+    pub additions: Style,
+    pub deletions: Style,
+    /// Cells of the heatmap from no commits to the busiest weeks
+    pub heatmap: [(&'static str, Color); 5],
+    pub monochrome: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        ThemeName::default().theme()
+    }
+}
+
+impl ThemeName {
+    pub fn theme(self) -> Theme {
+        let fg = |color: Color| Style::default().fg(color);
+        let heatmap = |colors: [Color; 5]| colors.map(|color| ("■", color));
+        match self {
+            ThemeName::Dark => Theme {
+                header: fg(Color::Yellow),
+                date: fg(Color::Blue),
+                quota: fg(Color::DarkGray),
+                input: fg(Color::Yellow),
+                error: fg(Color::Red),
+                placeholder: fg(Color::DarkGray),
+                label: Style::default(),
+                additions: fg(Color::Green),
+                deletions: fg(Color::Red),
+                heatmap: heatmap([SLATE.c800, GREEN.c900, GREEN.c700, GREEN.c500, GREEN.c300]),
+                monochrome: false,
+            },
+            ThemeName::Light => Theme {
+                header: fg(AMBER.c800),
+                date: fg(BLUE.c700),
+                quota: fg(SLATE.c500),
+                input: fg(AMBER.c800),
+                error: fg(RED.c700),
+                placeholder: fg(SLATE.c400),
+                label: Style::default(),
+                additions: fg(GREEN.c700),
+                deletions: fg(RED.c700),
+                heatmap: heatmap([SLATE.c200, GREEN.c200, GREEN.c400, GREEN.c600, GREEN.c800]),
+                monochrome: false,
+            },
+            ThemeName::HighContrast => Theme {
+                header: fg(Color::White).add_modifier(Modifier::BOLD),
+                date: fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+                quota: fg(Color::White),
+                input: fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+                error: fg(Color::LightRed).add_modifier(Modifier::BOLD),
+                placeholder: fg(Color::Gray),
+                label: Style::default().add_modifier(Modifier::BOLD),
+                additions: fg(Color::LightGreen),
+                deletions: fg(Color::LightRed),
+                heatmap: heatmap([
+                    Color::DarkGray,
+                    GREEN.c800,
+                    GREEN.c600,
+                    GREEN.c400,
+                    GREEN.c200,
+                ]),
+                monochrome: false,
+            },
+            ThemeName::Solarized => {
+                let base01 = Color::Rgb(0x58, 0x6e, 0x75);
+                let yellow = Color::Rgb(0xb5, 0x89, 0x00);
+                let red = Color::Rgb(0xdc, 0x32, 0x2f);
+                let green = Color::Rgb(0x85, 0x99, 0x00);
+                Theme {
+                    header: fg(yellow),
+                    date: fg(Color::Rgb(0x26, 0x8b, 0xd2)),
+                    quota: fg(base01),
+                    input: fg(yellow),
+                    error: fg(red),
+                    placeholder: fg(base01),
+                    label: Style::default(),
+                    additions: fg(green),
+                    deletions: fg(red),
+                    heatmap: heatmap([
+                        Color::Rgb(0x07, 0x36, 0x42),
+                        Color::Rgb(0x4a, 0x55, 0x00),
+                        Color::Rgb(0x68, 0x77, 0x00),
+                        green,
+                        Color::Rgb(0xb0, 0xc8, 0x20),
+                    ]),
+                    monochrome: false,
+                }
+            }
+            ThemeName::Monochrome => {
+                let bold = Style::default().add_modifier(Modifier::BOLD);
+                let dim = Style::default().add_modifier(Modifier::DIM);
+                Theme {
+                    header: bold,
+                    date: bold,
+                    quota: dim,
+                    input: Style::default(),
+                    error: bold,
+                    placeholder: dim,
+                    label: Style::default(),
+                    additions: Style::default(),
+                    deletions: dim,
+                    heatmap: [
+                        ("·", Color::Reset),
+                        ("░", Color::Reset),
+                        ("▒", Color::Reset),
+                        ("▓", Color::Reset),
+                        ("█", Color::Reset),
+                    ],
+                    monochrome: true,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_use_colours_in_monochrome() {
+        let theme = ThemeName::Monochrome.theme();
+        let styles = [
+            theme.header,
+            theme.date,
+            theme.quota,
+            theme.input,
+            theme.error,
+            theme.placeholder,
+            theme.label,
+            theme.additions,
+            theme.deletions,
+        ];
+        assert!(styles
+            .iter()
+            .all(|style| style.fg.is_none() && style.bg.is_none()));
+        assert!(theme
+            .heatmap
+            .iter()
+            .all(|(_, color)| *color == Color::Reset));
+        let mut cells: Vec<&str> = theme.heatmap.iter().map(|(cell, _)| *cell).collect();
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), 5);
+    }
+}
@@ -0,0 +1,14 @@
+/// Quotes the field when it contains a separator, a quote or a new line
+pub fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Line of comma separated fields, ending with a new line
+pub fn line<S: AsRef<str>>(fields: &[S]) -> String {
+    let fields: Vec<String> = fields.iter().map(|f| escape(f.as_ref())).collect();
+    format!("{}\n", fields.join(","))
+}
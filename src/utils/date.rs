@@ -1,10 +1,37 @@
 use chrono::{DateTime, Datelike};
 
 pub fn convert_timestamp_to_month_and_year(timestamp: &u32) -> String {
-    let naive_datetime = DateTime::from_timestamp(timestamp.clone() as i64, 0).unwrap();
+    let naive_datetime = DateTime::from_timestamp(*timestamp as i64, 0).unwrap_or_default();
     format!("{} {}", naive_datetime.format("%b"), naive_datetime.year())
 }
 
+/// Month spelled out for sentences, ie: June 2019
+pub fn convert_timestamp_to_month_name_and_year(timestamp: &u32) -> String {
+    let naive_datetime = DateTime::from_timestamp(*timestamp as i64, 0).unwrap_or_default();
+    format!("{} {}", naive_datetime.format("%B"), naive_datetime.year())
+}
+
+pub fn convert_timestamp_to_date(timestamp: &u32) -> String {
+    let naive_datetime = DateTime::from_timestamp(*timestamp as i64, 0).unwrap_or_default();
+    naive_datetime.format("%Y-%m-%d").to_string()
+}
+
+pub fn year_month(timestamp: &u32) -> (i32, u32) {
+    let naive_datetime = DateTime::from_timestamp(*timestamp as i64, 0).unwrap_or_default();
+    (naive_datetime.year(), naive_datetime.month())
+}
+
+pub fn convert_timestamp_to_time(timestamp: &u32) -> String {
+    let naive_datetime = DateTime::from_timestamp(*timestamp as i64, 0).unwrap_or_default();
+    naive_datetime.format("%H:%M UTC").to_string()
+}
+
+/// Year and week of the year (0 based) of the timestamp
+pub fn year_week(timestamp: &u32) -> (i32, u32) {
+    let naive_datetime = DateTime::from_timestamp(*timestamp as i64, 0).unwrap_or_default();
+    (naive_datetime.year(), naive_datetime.ordinal0() / 7)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -15,5 +42,21 @@ mod tests {
         let timestamp = 1361059200;
         let month = convert_timestamp_to_month_and_year(&timestamp);
         assert_eq!(month, "Feb 2013");
+        assert_eq!(
+            convert_timestamp_to_month_name_and_year(&timestamp),
+            "February 2013"
+        );
+        assert_eq!(convert_timestamp_to_date(&timestamp), "2013-02-17");
+    }
+
+    #[test]
+    fn should_convert_week_timestamp_to_year_week() {
+        assert_eq!(year_week(&1361059200), (2013, 6));
+        assert_eq!(year_month(&1361059200), (2013, 2));
+    }
+
+    #[test]
+    fn should_convert_timestamp_to_time() {
+        assert_eq!(convert_timestamp_to_time(&1700000000), "22:13 UTC");
     }
 }
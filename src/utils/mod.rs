@@ -1 +1,3 @@
+pub mod csv;
 pub mod date;
+pub mod number;